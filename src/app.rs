@@ -1,5 +1,9 @@
 use crate::config::Config;
 use crate::fetch::SystemInfo;
+use crate::fetch::providers::connections::{Connection, SocketState};
+use crate::fetch::providers::dir_size::{DirNode, DirScan};
+use crate::fetch::providers::package_history::PackageHistory;
+use crate::fetch::providers::packages::{PackageCount, PackageProvider};
 use std::sync::mpsc;
 use std::thread;
 
@@ -7,6 +11,8 @@ pub enum AppTab {
     Dashboard,
     Processes,
     Network,
+    Disks,
+    Packages,
     Settings,
 }
 
@@ -14,6 +20,85 @@ pub enum SettingsOption {
     RefreshRate,
     ThemeColor,
     ShowHints,
+    DiskIoGauge,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum NetworkView {
+    Interfaces,
+    Connections,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum DisksView {
+    Usage,
+    Devices,
+}
+
+/// ncdu-style explorer opened from a mount in the Disks tab
+pub struct DirExplorer {
+    pub scan: DirScan,
+    /// Child indices from the scan root down to the directory being viewed
+    pub path: Vec<usize>,
+    pub selected: usize,
+}
+
+impl DirExplorer {
+    pub fn new(mount_point: &str) -> Self {
+        Self {
+            scan: DirScan::start(std::path::Path::new(mount_point)),
+            path: Vec::new(),
+            selected: 0,
+        }
+    }
+
+    /// The directory currently shown, None while the scan is running
+    pub fn current(&self) -> Option<&DirNode> {
+        let mut node = self.scan.result.as_ref()?;
+        for &i in &self.path {
+            node = node.children.get(i)?;
+        }
+        Some(node)
+    }
+
+    /// Path of the directory currently shown, for the title
+    pub fn current_path(&self) -> std::path::PathBuf {
+        let mut path = self.scan.root.clone();
+        let mut node = self.scan.result.as_ref();
+        for &i in &self.path {
+            node = node.and_then(|n| n.children.get(i));
+            if let Some(n) = node {
+                path.push(&n.name);
+            }
+        }
+        path
+    }
+
+    pub fn enter(&mut self) {
+        if let Some(dir) = self.current()
+            && self.selected < dir.children.len()
+        {
+            self.path.push(self.selected);
+            self.selected = 0;
+        }
+    }
+
+    pub fn up(&mut self) {
+        if let Some(i) = self.path.pop() {
+            self.selected = i;
+        }
+    }
+
+    pub fn next(&mut self) {
+        let len = self.current().map_or(0, |d| d.children.len());
+        if self.selected < len.saturating_sub(1) {
+            self.selected += 1;
+        }
+    }
+
+    pub fn previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
 }
 
 #[derive(Clone, Copy)]
@@ -40,15 +125,31 @@ pub struct App {
     pub config: Config,
     pub process_scroll: usize,
     pub process_sort: ProcessSortMode, // New: Sort mode
+    /// Dashboard shows usage per logical CPU instead of one CPU gauge
+    pub show_per_core: bool,
+    pub network_selected: usize,
+    pub network_view: NetworkView,
+    pub connection_scroll: usize,
+    /// Only show connections in this state
+    pub connection_filter: Option<SocketState>,
+    pub listening_only: bool,
+    pub disk_selected: usize,
+    pub disk_view: DisksView,
+    pub block_device_scroll: usize,
+    pub explorer: Option<DirExplorer>,
+    pub package_scroll: usize,
     pub show_help: bool,
     pub show_hints: bool, // New: Hints toggle
+    /// Distro ID forced with --distro, used instead of os-release for the logo
+    pub distro_override: Option<String>,
 
     // Settings state
     pub settings_index: usize,
     pub refresh_rate_ms: u64,
 
     // Async state
-    pub package_rx: mpsc::Receiver<String>,
+    pub package_rx: mpsc::Receiver<PackageCount>,
+    pub package_history_rx: mpsc::Receiver<Option<PackageHistory>>,
 }
 
 impl Default for App {
@@ -61,37 +162,73 @@ impl App {
     pub fn new() -> Self {
         let config = Config::load();
 
-        // Setup Async Package Fetching
-        let (tx, rx) = mpsc::channel();
+        // Setup Async Package Fetching (one counter thread per package manager)
+        let rx = PackageProvider::spawn_counters();
+
+        // Package logs can be several MB, parse them off the UI thread too
+        let (history_tx, history_rx) = mpsc::channel();
+        let history_limit = config.package_history_limit;
         thread::spawn(move || {
-            // We use the full path to the provider
-            let count = crate::fetch::providers::packages::PackageProvider::count_packages();
-            let _ = tx.send(count);
+            let _ = history_tx.send(PackageHistory::new(history_limit));
         });
 
         Self {
             should_quit: false,
             current_tab: AppTab::Dashboard,
-            system_info: SystemInfo::new(),
+            system_info: SystemInfo::new(&config),
             process_scroll: 0,
             process_sort: ProcessSortMode::Cpu,
+            show_per_core: false,
+            network_selected: 0,
+            network_view: NetworkView::Interfaces,
+            connection_scroll: 0,
+            connection_filter: None,
+            listening_only: false,
+            disk_selected: 0,
+            disk_view: DisksView::Usage,
+            block_device_scroll: 0,
+            explorer: None,
+            package_scroll: 0,
             show_help: false,
             show_hints: true, // Default to true
+            distro_override: None,
             settings_index: 0,
             refresh_rate_ms: config.refresh_rate,
             config,
             package_rx: rx,
+            package_history_rx: history_rx,
         }
     }
 
     pub fn on_tick(&mut self) {
-        // Check for async updates
-        if let Ok(pkg_count) = self.package_rx.try_recv() {
-            self.system_info.packages = pkg_count;
+        // Check for async updates; each package manager reports as soon as it is done
+        if self.system_info.packages_pending {
+            loop {
+                match self.package_rx.try_recv() {
+                    Ok(pkg_count) => self.system_info.add_package_count(pkg_count),
+                    Err(mpsc::TryRecvError::Empty) => break,
+                    Err(mpsc::TryRecvError::Disconnected) => {
+                        self.system_info.packages_pending = false;
+                        break;
+                    }
+                }
+            }
+        }
+
+        if let Some(explorer) = &mut self.explorer {
+            explorer.scan.poll();
+        }
+
+        if let Ok(history) = self.package_history_rx.try_recv() {
+            self.system_info.package_history = history;
+            self.system_info.package_history_pending = false;
         }
 
         // Only refresh processes if we are on the Processes tab
-        let update_processes = matches!(self.current_tab, AppTab::Processes);
+        // (or the Connections view, which names socket owners from them)
+        let show_connections = matches!(self.current_tab, AppTab::Network)
+            && self.network_view == NetworkView::Connections;
+        let update_processes = matches!(self.current_tab, AppTab::Processes) || show_connections;
         // Pass sort mode to update logic?
         // Actually, we can just sort in place here or in SystemInfo.
         // Let's modify SystemInfo::refresh to take the sort mode?
@@ -100,13 +237,19 @@ impl App {
         // We need to update SystemInfo signature first. For now, let's pass it.
         self.system_info
             .refresh(update_processes, self.process_sort);
+
+        if show_connections {
+            self.system_info.refresh_connections();
+        }
     }
 
     pub fn next_tab(&mut self) {
         self.current_tab = match self.current_tab {
             AppTab::Dashboard => AppTab::Processes,
             AppTab::Processes => AppTab::Network,
-            AppTab::Network => AppTab::Settings,
+            AppTab::Network => AppTab::Disks,
+            AppTab::Disks => AppTab::Packages,
+            AppTab::Packages => AppTab::Settings,
             AppTab::Settings => AppTab::Dashboard,
         };
     }
@@ -116,7 +259,9 @@ impl App {
             AppTab::Dashboard => AppTab::Settings,
             AppTab::Processes => AppTab::Dashboard,
             AppTab::Network => AppTab::Processes,
-            AppTab::Settings => AppTab::Network,
+            AppTab::Disks => AppTab::Network,
+            AppTab::Packages => AppTab::Disks,
+            AppTab::Settings => AppTab::Packages,
         };
     }
 
@@ -133,10 +278,175 @@ impl App {
         }
     }
 
+    // Network tab navigation
+    pub fn network_next(&mut self) {
+        match self.network_view {
+            NetworkView::Interfaces => {
+                if self.network_selected < self.system_info.networks.len().saturating_sub(1) {
+                    self.network_selected += 1;
+                }
+            }
+            NetworkView::Connections => {
+                if self.connection_scroll < self.visible_connections().len().saturating_sub(1) {
+                    self.connection_scroll += 1;
+                }
+            }
+        }
+    }
+
+    pub fn network_previous(&mut self) {
+        match self.network_view {
+            NetworkView::Interfaces => {
+                self.network_selected = self.network_selected.saturating_sub(1)
+            }
+            NetworkView::Connections => {
+                self.connection_scroll = self.connection_scroll.saturating_sub(1)
+            }
+        }
+    }
+
+    pub fn toggle_per_core(&mut self) {
+        self.show_per_core = !self.show_per_core;
+    }
+
+    pub fn toggle_network_view(&mut self) {
+        self.network_view = match self.network_view {
+            NetworkView::Interfaces => {
+                self.connection_scroll = 0;
+                NetworkView::Connections
+            }
+            NetworkView::Connections => NetworkView::Interfaces,
+        };
+    }
+
+    /// Cycles the state filter: all -> ESTAB -> LISTEN -> ... -> all
+    pub fn cycle_connection_filter(&mut self) {
+        let filters = SocketState::FILTERS;
+        self.connection_filter = match self.connection_filter {
+            None => Some(filters[0]),
+            Some(current) => filters
+                .iter()
+                .position(|&f| f == current)
+                .and_then(|i| filters.get(i + 1).copied()),
+        };
+        self.connection_scroll = 0;
+    }
+
+    pub fn toggle_listening_only(&mut self) {
+        self.listening_only = !self.listening_only;
+        self.connection_scroll = 0;
+    }
+
+    /// Connections after the state filter and listening-only mode
+    pub fn visible_connections(&self) -> Vec<&Connection> {
+        self.system_info
+            .connections
+            .connections
+            .iter()
+            .filter(|c| self.connection_filter.is_none_or(|f| c.state == f))
+            .filter(|c| !self.listening_only || c.is_listening())
+            .collect()
+    }
+
+    // Disks tab navigation
+    pub fn disk_next(&mut self) {
+        if let Some(explorer) = &mut self.explorer {
+            explorer.next();
+            return;
+        }
+        match self.disk_view {
+            DisksView::Usage => {
+                if self.disk_selected < self.system_info.disks.len().saturating_sub(1) {
+                    self.disk_selected += 1;
+                }
+            }
+            DisksView::Devices => {
+                let rows: usize = self
+                    .system_info
+                    .block_devices
+                    .iter()
+                    .map(|dev| dev.tree_rows())
+                    .sum();
+                if self.block_device_scroll < rows.saturating_sub(1) {
+                    self.block_device_scroll += 1;
+                }
+            }
+        }
+    }
+
+    pub fn disk_previous(&mut self) {
+        if let Some(explorer) = &mut self.explorer {
+            explorer.previous();
+            return;
+        }
+        match self.disk_view {
+            DisksView::Usage => self.disk_selected = self.disk_selected.saturating_sub(1),
+            DisksView::Devices => {
+                self.block_device_scroll = self.block_device_scroll.saturating_sub(1)
+            }
+        }
+    }
+
+    /// Enter: open the explorer on the selected mount, or drill into the selected directory
+    pub fn disk_enter(&mut self) {
+        if let Some(explorer) = &mut self.explorer {
+            explorer.enter();
+        } else if self.disk_view == DisksView::Usage
+            && let Some(disk) = self.system_info.disks.get(self.disk_selected)
+        {
+            self.explorer = Some(DirExplorer::new(&disk.mount_point));
+        }
+    }
+
+    /// Backspace: go up one directory, closing the explorer at the mount root
+    pub fn disk_back(&mut self) {
+        match &mut self.explorer {
+            Some(explorer) if !explorer.path.is_empty() => explorer.up(),
+            _ => self.close_explorer(),
+        }
+    }
+
+    pub fn close_explorer(&mut self) {
+        if let Some(explorer) = self.explorer.take() {
+            explorer.scan.cancel();
+        }
+    }
+
+    pub fn toggle_disk_view(&mut self) {
+        self.close_explorer();
+        self.disk_view = match self.disk_view {
+            DisksView::Usage => {
+                // The device tree rarely changes, rescan only when it is opened
+                self.system_info.refresh_block_devices();
+                self.block_device_scroll = 0;
+                DisksView::Devices
+            }
+            DisksView::Devices => DisksView::Usage,
+        };
+    }
+
+    // Package history navigation
+    pub fn package_scroll_down(&mut self) {
+        let len = self
+            .system_info
+            .package_history
+            .as_ref()
+            .map_or(0, |h| h.transactions.len());
+        if self.package_scroll < len.saturating_sub(1) {
+            self.package_scroll += 1;
+        }
+    }
+
+    pub fn package_scroll_up(&mut self) {
+        if self.package_scroll > 0 {
+            self.package_scroll -= 1;
+        }
+    }
+
     // Settings navigation
     pub fn settings_next(&mut self) {
-        // We have 3 settings: Refresh Rate (0), Theme Color (1), Show Hints (2)
-        if self.settings_index < 2 {
+        // We have 4 settings: Refresh Rate (0), Theme Color (1), Show Hints (2), Disk I/O Gauge (3)
+        if self.settings_index < 3 {
             self.settings_index += 1;
         }
     }
@@ -182,6 +492,11 @@ impl App {
                 // Show Hints: Toggle
                 self.show_hints = !self.show_hints;
             }
+            3 => {
+                // Disk I/O Gauge: Toggle
+                self.config.show_disk_io_gauge = !self.config.show_disk_io_gauge;
+                let _ = self.config.save();
+            }
             _ => {}
         }
     }
@@ -10,15 +10,64 @@ pub struct Config {
     pub refresh_rate: u64,
     #[serde(default = "default_module_order")]
     pub modules: Vec<String>,
+    /// Number of package transactions kept for the Packages tab
+    #[serde(default = "default_package_history_limit")]
+    pub package_history_limit: usize,
+    #[serde(default)]
+    pub disks: DiskConfig,
+    #[serde(default)]
+    pub network: NetworkConfig,
+    #[serde(default)]
+    pub shell: ShellConfig,
+    /// Extra dashboard gauge showing the busiest block device
+    #[serde(default)]
+    pub show_disk_io_gauge: bool,
+}
+
+/// Which mount points the Disk module and Disks tab show.
+/// Entries are mount points; a trailing '*' matches by prefix (e.g. "/media/*").
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct DiskConfig {
+    /// If non-empty, only these mount points are shown
+    pub include: Vec<String>,
+    /// Mount points to hide
+    pub exclude: Vec<String>,
+    /// Show overlay, efivarfs, snap and other pseudo filesystems
+    pub show_pseudo: bool,
+}
+
+/// Which interfaces the Network tab shows.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct NetworkConfig {
+    /// Hide loopback, bridges, veth, tun/tap, wireguard and other software interfaces
+    pub hide_virtual: bool,
+}
+
+/// Extra details appended to the Shell module.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct ShellConfig {
+    /// Prompt themes and frameworks (Starship, Oh My Zsh, Powerlevel10k, ...)
+    pub show_prompt: bool,
+    /// Number of plugins the shell's plugin manager loads
+    pub show_plugins: bool,
+}
+
+fn default_package_history_limit() -> usize {
+    50
 }
 
 fn default_module_order() -> Vec<String> {
     vec![
         "os".to_string(),
+        "hostname".to_string(),
         "host".to_string(),
         "kernel".to_string(),
         "uptime".to_string(),
         "packages".to_string(),
+        "last_upgrade".to_string(),
         "shell".to_string(),
         "display".to_string(),
         "de".to_string(),
@@ -29,6 +78,8 @@ fn default_module_order() -> Vec<String> {
         "font".to_string(),
         "cursor".to_string(),
         "terminal".to_string(),
+        "terminal_font".to_string(),
+        "terminal_theme".to_string(),
         "cpu".to_string(),
         "gpu".to_string(),
         "memory".to_string(),
@@ -65,6 +116,11 @@ impl Default for Config {
                 gauge_ram: "#ff00ff".to_string(),      // Magenta (Hex)
             },
             modules: default_module_order(),
+            package_history_limit: default_package_history_limit(),
+            disks: DiskConfig::default(),
+            network: NetworkConfig::default(),
+            shell: ShellConfig::default(),
+            show_disk_io_gauge: false,
         }
     }
 }
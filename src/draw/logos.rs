@@ -1,54 +1,499 @@
-pub fn get_logo(os_name: &str) -> Vec<&'static str> {
-    match os_name.to_lowercase() {
-        n if n.contains("arch") => vec![
-            "       /\\       ",
-            "      /  \\      ",
-            "     /    \\     ",
-            "    /      \\    ",
-            "   /   ,,   \\   ",
-            "  /   |  |   \\  ",
-            " /_-''    ''-_\\ ",
-        ],
-        n if n.contains("debian") => vec![
-            "  _____  ",
-            " /  __ \\ ",
-            "|  /    |",
-            "|  \\___/ ",
-            " \\____   ",
-            "      \\  ",
-            "  ____/  ",
-        ],
-        n if n.contains("ubuntu") => vec![
-            "           _  ",
-            "         -   -",
-            "       -   _   -",
-            "      -  _| |_  -",
-            "     -  |_   _|  -",
-            "      -   |_|   -",
-            "       -       -",
-            "         - _ -",
-        ],
-        n if n.contains("fedora") => vec![
-            "      _____",
-            "     /   __)",
-            "     |  /  ",
-            "  ___|  |__",
-            " (___    __)",
-            "    |   |  ",
-            "    |___|  ",
-        ],
-        n if n.contains("windows") => vec![
-            " ,.=:!!t3Z3z.,",
-            " :tt:::tt333EE3",
-            " Et:::ztt33EEEL",
-            " ;3=*^\"\"\"\"\"*4EE",
-        ],
-        _ => vec![
-            "   _______   ",
-            "  |       |  ",
-            "  | MIZU  |  ",
-            "  | FETCH |  ",
-            "  |_______|  ",
-        ],
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+
+/// A distro logo. Lines may contain `$1`..`$9` to switch to the matching entry
+/// of `colors`; a color stays active across lines until the next marker.
+pub struct Logo {
+    /// os-release IDs (ID or ID_LIKE entries) this logo is used for
+    pub ids: &'static [&'static str],
+    pub colors: &'static [Color],
+    pub large: &'static [&'static str],
+    /// Used in compact mode or when the large logo doesn't fit
+    pub small: &'static [&'static str],
+}
+
+impl Logo {
+    fn art(&self, compact: bool) -> &'static [&'static str] {
+        if compact { self.small } else { self.large }
+    }
+
+    /// Width in cells, without color markers
+    pub fn width(&self, compact: bool) -> usize {
+        self.art(compact)
+            .iter()
+            .map(|line| strip_markers(line).chars().count())
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub fn height(&self, compact: bool) -> usize {
+        self.art(compact).len()
+    }
+
+    /// Colored lines, padded to the same width so centering keeps the shape intact
+    pub fn lines(&self, compact: bool) -> Vec<Line<'static>> {
+        let width = self.width(compact);
+        let mut color = self.colors.first().copied().unwrap_or(Color::Reset);
+
+        self.art(compact)
+            .iter()
+            .map(|line| {
+                let mut spans = Vec::new();
+                let mut text = String::new();
+                let mut chars = line.chars().peekable();
+                while let Some(c) = chars.next() {
+                    let index = chars.peek().and_then(|d| d.to_digit(10));
+                    match index {
+                        Some(index) if c == '$' && index > 0 => {
+                            chars.next();
+                            if !text.is_empty() {
+                                spans.push(Span::styled(
+                                    std::mem::take(&mut text),
+                                    Style::default().fg(color),
+                                ));
+                            }
+                            color = self
+                                .colors
+                                .get(index as usize - 1)
+                                .copied()
+                                .unwrap_or(color);
+                        }
+                        _ => text.push(c),
+                    }
+                }
+                let padding = width.saturating_sub(strip_markers(line).chars().count());
+                text.push_str(&" ".repeat(padding));
+                spans.push(Span::styled(text, Style::default().fg(color)));
+                Line::from(spans)
+            })
+            .collect()
     }
 }
+
+fn strip_markers(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '$'
+            && chars
+                .peek()
+                .is_some_and(|d| d.is_ascii_digit() && *d != '0')
+        {
+            chars.next();
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Picks the logo for the first ID that has one (os-release ID, then ID_LIKE),
+/// falling back to the generic box.
+pub fn get_logo(ids: &[&str]) -> &'static Logo {
+    ids.iter()
+        .find_map(|id| {
+            let id = id.to_lowercase();
+            LOGOS.iter().find(|logo| logo.ids.contains(&id.as_str()))
+        })
+        .unwrap_or(&GENERIC)
+}
+
+static GENERIC: Logo = Logo {
+    ids: &[],
+    colors: &[Color::Cyan],
+    large: &[
+        r"   _______   ",
+        r"  |       |  ",
+        r"  | MIZU  |  ",
+        r"  | FETCH |  ",
+        r"  |_______|  ",
+    ],
+    small: &[r" _____ ", r"| ~~~ |", r"|_____|"],
+};
+
+static LOGOS: &[Logo] = &[
+    Logo {
+        ids: &["arch", "archarm"],
+        colors: &[Color::Cyan, Color::Blue],
+        large: &[
+            r"$1         /\         ",
+            r"        /  \        ",
+            r"       /\   \       ",
+            r"      /      \      ",
+            r"     /   ,,   \     ",
+            r"$2    /   |  |  -\    ",
+            r"   /_-''    ''-_\   ",
+        ],
+        small: &[r"$1   /\   ", r"  /  \  ", r"$2 / /\ \ ", r"/_/  \_\"],
+    },
+    Logo {
+        ids: &["endeavouros"],
+        colors: &[Color::Magenta, Color::Red, Color::Blue],
+        large: &[
+            r"$2          /$1o$3.",
+            r"$2        /$1sssso$3-",
+            r"$2      /$1ossssssso$3:",
+            r"$2    /$1ssssssssssso$3+",
+            r"$2  /$1ssssssssssssssso$3+",
+            r"$2//$1osssssssssssssso$3+-",
+            r"$3 `+++++++++++++++-`",
+        ],
+        small: &[
+            r"$2   /$1o$3.",
+            r"$2 /$1ssso$3-",
+            r"$2/$1ssssso$3+",
+            r"$3`++++++`",
+        ],
+    },
+    Logo {
+        ids: &["manjaro"],
+        colors: &[Color::Green],
+        large: &[
+            r"$1██████████████  ████████",
+            r"██████████████  ████████",
+            r"████████        ████████",
+            r"████████  ████  ████████",
+            r"████████  ████  ████████",
+            r"████████  ████  ████████",
+            r"████████  ████  ████████",
+        ],
+        small: &[r"$1██████ ███", r"███    ███", r"███ ██ ███", r"███ ██ ███"],
+    },
+    Logo {
+        ids: &["nixos"],
+        colors: &[Color::Blue, Color::Cyan],
+        large: &[
+            r"$1  \\  $2\\ //   ",
+            r"$1 ==\\__$2\\/ // ",
+            r"$1   //   $2\\//  ",
+            r"$1==//     $2//== ",
+            r"$1 //\\___$2//    ",
+            r"$1// /\\  $2\\==  ",
+            r"$1  // \\  $2\\   ",
+        ],
+        small: &[
+            r"$1 \\ $2\\//",
+            r"$1==\\$2\//=",
+            r"$1 //$2\\  ",
+            r"$1//  $2\\ ",
+        ],
+    },
+    Logo {
+        ids: &["gentoo", "funtoo"],
+        colors: &[Color::Magenta, Color::White],
+        large: &[
+            r"$1   _-----_    ",
+            r"  (       \   ",
+            r"  \    $20$1   \  ",
+            r"   \        ) ",
+            r"   /      _/  ",
+            r"  (     _-    ",
+            r"  \____-      ",
+        ],
+        small: &[r"$1 _---_ ", r"(  $2o$1  \", r" \   _/", r"(__-   "],
+    },
+    Logo {
+        ids: &["void"],
+        colors: &[Color::Green, Color::DarkGray],
+        large: &[
+            r"$1    _______    ",
+            r" _ \______ -   ",
+            r"| \  $2___$1  \ |  ",
+            r"| | $2/   \$1 | |  ",
+            r"| | $2\___/$1 | |  ",
+            r"| \______ \_|  ",
+            r" -_______\     ",
+        ],
+        small: &[r"$1 _____ ", r"/ $2___$1 \", r"| $2\_/$1 |", r"\_____/"],
+    },
+    Logo {
+        ids: &[
+            "opensuse",
+            "opensuse-tumbleweed",
+            "opensuse-leap",
+            "opensuse-microos",
+            "suse",
+            "sles",
+        ],
+        colors: &[Color::Green],
+        large: &[
+            r"$1  _______     ",
+            r"__|   __ \    ",
+            r"     / .\ \   ",
+            r"     \__/ |   ",
+            r"   _______|   ",
+            r"   \_______   ",
+            r"__________/   ",
+        ],
+        small: &[r"$1 ____  ", r"|  _ \ ", r"  (_) |", r"______/"],
+    },
+    Logo {
+        ids: &["alpine"],
+        colors: &[Color::Blue, Color::White],
+        large: &[
+            r"$1     /\ /\      ",
+            r"    /$2/ $1\  \     ",
+            r"   /$2/   $1\  \    ",
+            r"  /$2//    $1\  \   ",
+            r" /$2//      $1\  \  ",
+            r"          \     ",
+        ],
+        small: &[
+            r"$1   /\ /\  ",
+            r"  /$2/$1\  \ ",
+            r" /$2/  $1\  \",
+            r"        \ ",
+        ],
+    },
+    Logo {
+        ids: &["linuxmint"],
+        colors: &[Color::Green, Color::White],
+        large: &[
+            r"$1 ___________   ",
+            r"|_          \  ",
+            r"  | $2| _____ $1|  ",
+            r"  | $2| | | | $1|  ",
+            r"  | $2| | | | $1|  ",
+            r"  | $2\_____/ $1|  ",
+            r"  \_________/  ",
+        ],
+        small: &[r"$1 ______ ", r"|_ $2.-.$1 \", r" |$2| | |$1|", r" \____/ "],
+    },
+    Logo {
+        ids: &["pop"],
+        colors: &[Color::Cyan, Color::White],
+        large: &[
+            r"$1______               ",
+            r"\   _ \        __    ",
+            r" \ \ \ \      / /    ",
+            r"  \ \_\ \    / /     ",
+            r"   \  ___\  /_/      ",
+            r"    \ \    _         ",
+            r"   __\_\__(_)_       ",
+            r"  (___________)`     ",
+        ],
+        small: &[r"$1____    _ ", r"\ _ \  / /", r" \ __\/_/ ", r" _\_\(_)_ "],
+    },
+    Logo {
+        ids: &["kali"],
+        colors: &[Color::Blue, Color::White],
+        large: &[
+            r"$1-#. #                  ",
+            r"  @###. .              ",
+            r"    ######@########..  ",
+            r"      .############### ",
+            r"    ########## @@  .   ",
+            r"   ###########.        ",
+            r"      ###$2@@@$1####.      ",
+            r"           ##  #.      ",
+        ],
+        small: &[
+            r"$1-#. #        ",
+            r"  @####@###. ",
+            r"   ######  . ",
+            r"      $2@@$1##.  ",
+        ],
+    },
+    Logo {
+        ids: &["rocky"],
+        colors: &[Color::Green],
+        large: &[
+            r"$1    __wgliliiligw_,    ",
+            r"   g@@@@@@@@@@@@@@@g   ",
+            r" g@@@@@@@@@@@@@@@@@@g  ",
+            r"g@@@@@@@@@@@@@@@@@@@@g ",
+            r"@@@@@@@@@@@@@@@@**@@@@@",
+            r"*@@@@@@@@@@@@@*   *@@* ",
+            r" *@@@@@@@@@*  _-   *   ",
+            r"   *@@@@@* _-          ",
+        ],
+        small: &[r"$1  ______  ", r" /@@@@@@\ ", r"|@@@@@*@@|", r" \@@* \_/ "],
+    },
+    Logo {
+        ids: &["almalinux"],
+        colors: &[Color::Red, Color::Yellow, Color::Blue, Color::Green],
+        large: &[
+            r"$1   ooo       $2 ,,,    ",
+            r"$1 oooooo    $2 ,,,,,    ",
+            r"$1  oooooo  $2 ,,,,,     ",
+            r"$4   ''     $3  ####     ",
+            r"$4 '''''    $3 ######    ",
+            r"$4  '''''   $3  ######   ",
+            r"$4    ''    $3    ##     ",
+        ],
+        small: &[
+            r"$1 oo  $2 ,, ",
+            r"$1oooo $2,,, ",
+            r"$4 ''  $3 ## ",
+            r"$4'''  $3### ",
+        ],
+    },
+    Logo {
+        ids: &["debian", "raspbian"],
+        colors: &[Color::Red],
+        large: &[
+            r"$1  _____  ",
+            r" /  __ \ ",
+            r"|  /    |",
+            r"|  \___/ ",
+            r" \____   ",
+            r"      \  ",
+            r"  ____/  ",
+        ],
+        small: &[r"$1  _____ ", r" /  __ \", r"|  (__/ ", r" \___   "],
+    },
+    Logo {
+        ids: &["ubuntu", "kubuntu", "xubuntu", "lubuntu"],
+        colors: &[Color::Red, Color::White],
+        large: &[
+            r"$1           _      ",
+            r"         -   -    ",
+            r"       -   _   -  ",
+            r"      -  $2_| |_$1  - ",
+            r"     -  $2|_   _|$1  -",
+            r"      -   $2|_|$1   - ",
+            r"       -       -  ",
+            r"         - _ -    ",
+        ],
+        small: &[
+            r"$1   _  _  ",
+            r" -  $2(_)$1 - ",
+            r"-  $2(_)$1   -",
+            r" - _ $2(_)$1  ",
+        ],
+    },
+    Logo {
+        ids: &["fedora", "nobara", "ultramarine"],
+        colors: &[Color::Blue, Color::White],
+        large: &[
+            r"$1      _____  ",
+            r"     /   __) ",
+            r"     |  /    ",
+            r"  ___|  |__  ",
+            r" (___    __) ",
+            r"    |   |    ",
+            r"    |___|    ",
+        ],
+        small: &[
+            r"$1   ___ ",
+            r"  | __)",
+            r" _|$2_|$1_ ",
+            r"(_   _)",
+            r"  |_|  ",
+        ],
+    },
+    Logo {
+        ids: &["centos", "rhel"],
+        colors: &[Color::Yellow, Color::Green, Color::Blue, Color::Magenta],
+        large: &[
+            r"$1 ____$2^$3____  ",
+            r"$1 |\  $2|$3  /|  ",
+            r"$1 | \ $2|$3 / |  ",
+            r"$4<---- $2---->  ",
+            r"$3 | / $4|$1 \ |  ",
+            r"$3 |/__$4|$1__\|  ",
+            r"$4     v       ",
+        ],
+        small: &[r"$1 _$2^$3_ ", r"$1|\$2|$3/|", r"$4<-$2+->", r"$3|/$4|$1\|"],
+    },
+    Logo {
+        ids: &["elementary"],
+        colors: &[Color::White],
+        large: &[
+            r"$1   ________   ",
+            r"  /  _____ \  ",
+            r" /  /    / \\ ",
+            r"|  |    /  ||",
+            r"|  \___/  / |",
+            r" \ _____-' / ",
+            r"  \_______/  ",
+        ],
+        small: &[r"$1  _____ ", r" / __  \", r"| (__/ |", r" \____/ "],
+    },
+    Logo {
+        ids: &["zorin"],
+        colors: &[Color::Blue],
+        large: &[
+            r"$1   ________   ",
+            r"  /________\  ",
+            r" /   ____   \ ",
+            r"|   /___/    |",
+            r"|     /___/  |",
+            r" \  ________/ ",
+            r"  \________/  ",
+        ],
+        small: &[r"$1 _____ ", r"/_____\", r"| /_/ |", r"\_____/"],
+    },
+    Logo {
+        ids: &["garuda"],
+        colors: &[Color::Red, Color::Magenta],
+        large: &[
+            r"$1      .----------.    ",
+            r"     /  .------.  \   ",
+            r"    /  /  $2___$1   \  \  ",
+            r"   /  /  $2/__/$1    \  \ ",
+            r"   \  \          /  / ",
+            r"    \  '--------'  /  ",
+            r"     '------------'   ",
+        ],
+        small: &[
+            r"$1  .----.  ",
+            r" / $2.--.$1 \ ",
+            r" \ $2'--'$1 / ",
+            r"  '----'  ",
+        ],
+    },
+    Logo {
+        ids: &["artix"],
+        colors: &[Color::Cyan],
+        large: &[
+            r"$1        /\        ",
+            r"       /  \       ",
+            r"      /`'.,\      ",
+            r"     /     ',     ",
+            r"    /      ,`\    ",
+            r"   /   ,.'`.  \   ",
+            r"  /.,'`     `'.\  ",
+        ],
+        small: &[r"$1   /\   ", r"  /.,\  ", r" / ,` \ ", r"/,'  '.\"],
+    },
+    Logo {
+        ids: &["cachyos"],
+        colors: &[Color::Cyan, Color::Green],
+        large: &[
+            r"$1    __________   ",
+            r"   /          \  $2o",
+            r"$1  /   ______  \   ",
+            r" /   /      \__/ $2o",
+            r"$1 \   \______      ",
+            r"  \          \   $2o",
+            r"$1   \__________\   ",
+        ],
+        small: &[
+            r"$1 ______ $2o",
+            r"$1/  ___/  ",
+            r"\  \___ $2o",
+            r"$1 \_____\ ",
+        ],
+    },
+    Logo {
+        ids: &["slackware"],
+        colors: &[Color::Blue, Color::White],
+        large: &[
+            r"$1   ________   ",
+            r"  /  ______|  ",
+            r"  | |______   ",
+            r"  \______  \  ",
+            r"   ______| |  ",
+            r"  | |________/",
+            r"  |____________",
+        ],
+        small: &[
+            r"$1  _____ ",
+            r" / ____|",
+            r" \___ \ ",
+            r" ____) |",
+            r"|_____/ ",
+        ],
+    },
+];
@@ -1,21 +1,34 @@
 use crate::app::ProcessSortMode;
+use crate::config::{Config, DiskConfig, NetworkConfig};
 use sysinfo::{Disks, Networks, System};
 
+pub mod cache;
 pub mod providers;
 
 use providers::battery::BatteryInfo;
+use providers::block_devices::{BlockDevice, BlockDeviceProvider};
+use providers::cgroup::CgroupLimits;
+use providers::connections::ConnectionProvider;
 use providers::cpu::CpuInfo;
-use providers::display::DisplayInfo;
+use providers::display::{DisplayInfo, MonitorInfo};
 use providers::gpu::GpuInfo;
+use providers::host::HostInfo;
 use providers::memory::MemoryInfo;
+use providers::pressure::PressureInfo;
 use providers::style::StyleInfo;
+use providers::terminal_style::TerminalStyle;
+use providers::virt::VirtInfo;
 
 // New imports
-use providers::disk::DiskProvider;
+use providers::disk::{DiskInfo, DiskProvider};
+use providers::disk_io::DiskIoProvider;
 use providers::network::{NetworkInfo, NetworkProvider};
 use providers::os::OsInfo;
-// PackageProvider is used in App, not here directly for async reasons,
-// but SystemInfo holds the display string.
+use providers::package_history::PackageHistory;
+use providers::packages::{PackageCount, PackageProvider};
+use providers::routing::RoutingInfo;
+// PackageProvider counters run in App for async reasons,
+// but SystemInfo holds the results.
 
 pub struct ProcessInfo {
     pub pid: u32,
@@ -27,6 +40,8 @@ pub struct ProcessInfo {
 pub struct SystemInfo {
     // Modular Components
     pub os: OsInfo,
+    pub host: HostInfo,
+    pub virt: VirtInfo,
     pub cpu_info: CpuInfo,
 
     // Static / Lazy fields
@@ -36,38 +51,57 @@ pub struct SystemInfo {
     pub icons: String,
     pub font: String,
     pub cursor: String,
+    pub terminal_font: String,
+    pub terminal_theme: String,
     pub battery: String,
-    pub display: String,
+    pub displays: Vec<MonitorInfo>,
 
     // Dynamic Fields (Refreshed on tick)
     pub uptime: u64,
     pub cpu_usage: f32,
+    /// Usage of each logical CPU, in sysinfo order
+    pub core_usage: Vec<f32>,
     pub memory_used: u64,
     pub memory_total: u64,
     pub swap_used: u64,
     pub swap_total: u64,
-    pub disk_usage: String,
+    /// /proc/meminfo breakdown, zram, zswap and swap devices (host values)
+    pub memory: MemoryInfo,
+    pub pressure: PressureInfo,
+    /// Container limits; when set, memory_* and cpu_usage are relative to them
+    pub cgroup: CgroupLimits,
+    pub disks: Vec<DiskInfo>,
+    pub disk_io: DiskIoProvider,
+    pub block_devices: Vec<BlockDevice>,
     pub processes: Vec<ProcessInfo>,
     pub networks: Vec<NetworkInfo>,
     pub local_ip: String,
+    pub routing: RoutingInfo,
+    pub connections: ConnectionProvider,
 
     // Async Fields (Updated by App)
-    pub packages: String,
+    pub packages: Vec<PackageCount>,
+    pub packages_pending: bool,
+    pub package_history: Option<PackageHistory>,
+    pub package_history_pending: bool,
 
     // Private Handles
+    disk_config: DiskConfig,
+    network_config: NetworkConfig,
     sys: System,
     net_handle: Networks,
+    net_provider: NetworkProvider,
     disk_handle: Disks,
 }
 
 impl Default for SystemInfo {
     fn default() -> Self {
-        Self::new()
+        Self::new(&Config::default())
     }
 }
 
 impl SystemInfo {
-    pub fn new() -> Self {
+    pub fn new(config: &Config) -> Self {
         let mut sys = System::new();
 
         // Initialize handles
@@ -77,8 +111,9 @@ impl SystemInfo {
         // Refresh specific components for static info
         sys.refresh_cpu_usage();
 
-        // OS Info (Static)
-        let os = OsInfo::new(&mut sys);
+        // OS Info (Static), adjusted for containers
+        let virt = VirtInfo::new();
+        let os = OsInfo::new(&virt, &config.shell);
 
         // Lazy load CPU info
         sys.refresh_cpu_all();
@@ -88,7 +123,8 @@ impl SystemInfo {
         let gpu_info = GpuInfo::new();
 
         // Style Info
-        let style = StyleInfo::new(&sys);
+        let style = StyleInfo::new();
+        let terminal_style = TerminalStyle::new(&os.terminal);
 
         // Display Info
         let display = DisplayInfo::new();
@@ -98,14 +134,20 @@ impl SystemInfo {
 
         // Initial Memory Fetch
         let mem_info = MemoryInfo::new(&mut sys);
+        let cgroup = CgroupLimits::new(mem_info.total, sys.cpus().len());
 
         // Network & Disk initial fetch
-        let networks = NetworkProvider::get_networks(&net_handle);
-        let local_ip = NetworkProvider::get_local_ip(&net_handle);
-        let disk_usage = DiskProvider::get_disk_usage(&disk_handle);
-
-        Self {
+        let mut net_provider = NetworkProvider::new();
+        let networks = net_provider.get_networks(&net_handle, &config.network);
+        let routing = RoutingInfo::new();
+        let local_ip = NetworkProvider::get_local_ip(&net_handle, routing.uplink());
+        let disks = DiskProvider::get_disks(&disk_handle, &config.disks);
+        let disk_io = DiskIoProvider::new();
+
+        let mut info = Self {
             os,
+            host: HostInfo::new(),
+            virt,
             cpu_info,
             gpus: gpu_info.names,
             wm_theme: style.wm_theme,
@@ -113,23 +155,43 @@ impl SystemInfo {
             icons: style.icons,
             font: style.font,
             cursor: style.cursor,
+            terminal_font: terminal_style.font.unwrap_or_else(|| "Unknown".to_string()),
+            terminal_theme: terminal_style
+                .theme
+                .unwrap_or_else(|| "Unknown".to_string()),
             battery,
-            display,
+            displays: display.monitors,
             uptime: System::uptime(),
             cpu_usage: 0.0,
+            core_usage: Vec::new(),
             memory_used: mem_info.used,
             memory_total: mem_info.total,
             swap_used: mem_info.swap_used,
             swap_total: mem_info.swap_total,
-            disk_usage,
+            memory: mem_info,
+            pressure: PressureInfo::new(),
+            cgroup,
+            disks,
+            disk_io,
+            block_devices: Vec::new(), // Scanned on demand by the Disks tab
             processes: Vec::new(),
             networks,
             local_ip,
-            packages: "Calculating...".to_string(), // Async placeholder
+            routing,
+            connections: ConnectionProvider::new(), // Scanned while the Connections view is open
+            packages: Vec::new(),
+            packages_pending: true, // Async placeholder
+            package_history: None,
+            package_history_pending: true,
+            disk_config: config.disks.clone(),
+            network_config: config.network.clone(),
             sys,
             net_handle,
+            net_provider,
             disk_handle,
-        }
+        };
+        info.apply_cgroup_limits();
+        info
     }
 
     pub fn refresh(&mut self, update_processes: bool, sort_mode: ProcessSortMode) {
@@ -148,20 +210,42 @@ impl SystemInfo {
         // Update Dynamic Fields
         self.uptime = System::uptime();
         self.cpu_usage = self.sys.global_cpu_usage();
-
-        // MemoryInfo::new refreshes nothing inside (it assumes sys is fresh)
-        self.memory_used = self.sys.used_memory();
-        self.memory_total = self.sys.total_memory();
-        self.swap_used = self.sys.used_swap();
-        self.swap_total = self.sys.total_swap();
+        self.core_usage = self.sys.cpus().iter().map(|cpu| cpu.cpu_usage()).collect();
+        self.cpu_info.refresh_frequency();
+
+        self.memory.refresh(&self.sys);
+        self.pressure.refresh();
+        self.memory_used = self.memory.used;
+        self.memory_total = self.memory.total;
+        self.swap_used = self.memory.swap_used;
+        self.swap_total = self.memory.swap_total;
+        self.cgroup
+            .refresh(self.memory_total, self.sys.cpus().len());
+        self.apply_cgroup_limits();
 
         if update_processes {
             self.update_processes(sort_mode);
         }
 
-        self.networks = NetworkProvider::get_networks(&self.net_handle);
-        self.local_ip = NetworkProvider::get_local_ip(&self.net_handle);
-        self.disk_usage = DiskProvider::get_disk_usage(&self.disk_handle);
+        self.networks = self
+            .net_provider
+            .get_networks(&self.net_handle, &self.network_config);
+        self.routing = RoutingInfo::new();
+        self.local_ip = NetworkProvider::get_local_ip(&self.net_handle, self.routing.uplink());
+        self.disks = DiskProvider::get_disks(&self.disk_handle, &self.disk_config);
+        self.disk_io.refresh();
+    }
+
+    /// Inside a limited cgroup the host's totals are meaningless, so the
+    /// gauges and the Memory module report usage against the limit instead
+    fn apply_cgroup_limits(&mut self) {
+        if let (Some(limit), Some(used)) = (self.cgroup.memory_limit, self.cgroup.memory_used) {
+            self.memory_total = limit;
+            self.memory_used = used;
+        }
+        if let Some(usage) = self.cgroup.cpu_usage {
+            self.cpu_usage = usage;
+        }
     }
 
     fn update_processes(&mut self, sort_mode: ProcessSortMode) {
@@ -186,10 +270,10 @@ impl SystemInfo {
                 });
             }
             ProcessSortMode::Memory => {
-                processes.sort_by(|a, b| b.mem.cmp(&a.mem));
+                processes.sort_by_key(|p| std::cmp::Reverse(p.mem));
             }
             ProcessSortMode::Pid => {
-                processes.sort_by(|a, b| a.pid.cmp(&b.pid));
+                processes.sort_by_key(|p| p.pid);
             }
         }
 
@@ -197,6 +281,76 @@ impl SystemInfo {
         self.processes = processes;
     }
 
+    pub fn add_package_count(&mut self, count: PackageCount) {
+        self.packages.push(count);
+        self.packages.sort_by_key(|c| c.manager);
+    }
+
+    pub fn get_formatted_packages(&self) -> String {
+        match (self.packages.is_empty(), self.packages_pending) {
+            (true, true) => "Calculating...".to_string(),
+            (true, false) => "Unknown".to_string(),
+            (false, true) => format!("{}, ...", PackageProvider::format_counts(&self.packages)),
+            (false, false) => PackageProvider::format_counts(&self.packages),
+        }
+    }
+
+    pub fn get_formatted_last_upgrade(&self) -> String {
+        match &self.package_history {
+            Some(history) => history
+                .last_full_upgrade
+                .clone()
+                .unwrap_or_else(|| "Never".to_string()),
+            None if self.package_history_pending => "Calculating...".to_string(),
+            None => "Unknown".to_string(),
+        }
+    }
+
+    /// "linux, mesa, firefox (+9) on 2024-05-01"
+    pub fn get_formatted_recent_packages(&self) -> String {
+        let Some(history) = &self.package_history else {
+            return self.get_formatted_last_upgrade();
+        };
+        let Some(newest) = history.transactions.first() else {
+            return "None".to_string();
+        };
+
+        let latest: Vec<&str> = history.latest_day().map(|t| t.package.as_str()).collect();
+        let mut out = latest
+            .iter()
+            .take(3)
+            .copied()
+            .collect::<Vec<_>>()
+            .join(", ");
+        if latest.len() > 3 {
+            out.push_str(&format!(" (+{})", latest.len() - 3));
+        }
+        out.push_str(&format!(
+            " on {}",
+            newest.timestamp.get(..10).unwrap_or_default()
+        ));
+        out
+    }
+
+    pub fn refresh_block_devices(&mut self) {
+        self.block_devices = BlockDeviceProvider::scan(&self.disks);
+    }
+
+    /// Owners are named from the process table, so call this after a refresh with processes
+    pub fn refresh_connections(&mut self) {
+        self.connections.refresh(&self.sys);
+    }
+
+    /// Root filesystem summary for the compact dashboard view
+    pub fn get_formatted_root_disk(&self) -> String {
+        self.disks
+            .iter()
+            .find(|d| d.mount_point == "/")
+            .or(self.disks.first())
+            .map(|d| d.summary())
+            .unwrap_or_else(|| "Unknown".to_string())
+    }
+
     pub fn get_formatted_uptime(&self) -> String {
         let seconds = self.uptime;
         let days = seconds / 86400;
@@ -0,0 +1,39 @@
+use std::fs;
+use std::path::Path;
+
+/// The bits of /proc/<pid> needed to walk up the process tree, read without
+/// going through sysinfo so startup doesn't need a full process table refresh
+pub struct Process {
+    pub name: String,
+    pub ppid: u32,
+    pub exe: Option<String>,
+}
+
+impl Process {
+    pub fn read(pid: u32) -> Option<Self> {
+        let dir = Path::new("/proc").join(pid.to_string());
+        // "1234 (name with spaces) S 1200 ..." - the name may contain parens itself
+        let stat = fs::read_to_string(dir.join("stat")).ok()?;
+        let after_name = &stat[stat.rfind(')')? + 2..];
+        let ppid = after_name.split_whitespace().nth(1)?.parse().ok()?;
+
+        let exe = fs::read_link(dir.join("exe")).ok().map(|p| {
+            p.to_string_lossy()
+                .trim_end_matches(" (deleted)")
+                .to_string()
+        });
+        let comm = fs::read_to_string(dir.join("comm"))
+            .ok()?
+            .trim()
+            .to_string();
+        // comm is cut at 15 bytes ("gnome-terminal-"), take the full name from exe then
+        let name = match &exe {
+            Some(exe) if comm.len() >= 15 => Path::new(exe)
+                .file_name()
+                .map_or(comm.clone(), |n| n.to_string_lossy().to_string()),
+            _ => comm,
+        };
+
+        Some(Self { name, ppid, exe })
+    }
+}
@@ -9,6 +9,7 @@ pub struct BatteryInfo {
 }
 
 impl BatteryInfo {
+    #[allow(clippy::new_ret_no_self)]
     pub fn new() -> String {
         let mut battery_output = Vec::new();
 
@@ -23,26 +24,26 @@ impl BatteryInfo {
                     let cap_path = path.join("capacity");
                     let status_path = path.join("status");
 
-                    if let Ok(cap_str) = fs::read_to_string(cap_path) {
-                        if let Ok(status_str) = fs::read_to_string(status_path) {
-                            let cap = cap_str.trim().parse::<u8>().unwrap_or(0);
-                            let status = status_str.trim().to_string(); // Charging, Discharging, Full
-
-                            // Format: 100% [AC Connected] or similar
-                            // Map status to user friendly string if needed, but "Charging"/"Discharging" is fine.
-                            // The user example had: "100% [AC Connected]"
-                            // Let's try to mimic that slightly or just use the status.
-
-                            let pretty_status = match status.as_str() {
-                                "Charging" => "Charging",
-                                "Discharging" => "Discharging",
-                                "Full" => "Full",
-                                "Not charging" => "AC Connected", // Sometimes happens at 100%
-                                _ => &status,
-                            };
-
-                            battery_output.push(format!("{}% [{}]", cap, pretty_status));
-                        }
+                    if let Ok(cap_str) = fs::read_to_string(cap_path)
+                        && let Ok(status_str) = fs::read_to_string(status_path)
+                    {
+                        let cap = cap_str.trim().parse::<u8>().unwrap_or(0);
+                        let status = status_str.trim().to_string(); // Charging, Discharging, Full
+
+                        // Format: 100% [AC Connected] or similar
+                        // Map status to user friendly string if needed, but "Charging"/"Discharging" is fine.
+                        // The user example had: "100% [AC Connected]"
+                        // Let's try to mimic that slightly or just use the status.
+
+                        let pretty_status = match status.as_str() {
+                            "Charging" => "Charging",
+                            "Discharging" => "Discharging",
+                            "Full" => "Full",
+                            "Not charging" => "AC Connected", // Sometimes happens at 100%
+                            _ => &status,
+                        };
+
+                        battery_output.push(format!("{}% [{}]", cap, pretty_status));
                     }
                 }
             }
@@ -0,0 +1,239 @@
+use std::fs;
+use std::path::Path;
+
+use super::disk::DiskInfo;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum BlockDeviceKind {
+    Disk,
+    Partition,
+    Lvm,
+    Crypt,
+    DeviceMapper,
+    Raid,
+}
+
+impl BlockDeviceKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Disk => "disk",
+            Self::Partition => "part",
+            Self::Lvm => "lvm",
+            Self::Crypt => "crypt",
+            Self::DeviceMapper => "dm",
+            Self::Raid => "raid",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum MediaType {
+    Nvme,
+    Ssd,
+    Hdd,
+    Unknown,
+}
+
+impl MediaType {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Nvme => "NVMe",
+            Self::Ssd => "SSD",
+            Self::Hdd => "HDD",
+            Self::Unknown => "-",
+        }
+    }
+}
+
+pub struct BlockDevice {
+    /// Kernel name, e.g. "nvme0n1p2" or "dm-0"
+    pub name: String,
+    /// Device mapper name ("cryptroot", "vg0-root") for dm devices
+    pub mapper_name: Option<String>,
+    pub kind: BlockDeviceKind,
+    pub model: Option<String>,
+    pub size: u64,
+    pub media: MediaType,
+    pub removable: bool,
+    pub mountpoint: Option<String>,
+    pub children: Vec<BlockDevice>,
+}
+
+impl BlockDevice {
+    /// The name lsblk would show: mapper name for dm devices, kernel name otherwise
+    pub fn display_name(&self) -> &str {
+        self.mapper_name.as_deref().unwrap_or(&self.name)
+    }
+
+    /// Rows this device takes in the tree view: itself plus all descendants
+    pub fn tree_rows(&self) -> usize {
+        1 + self.children.iter().map(Self::tree_rows).sum::<usize>()
+    }
+}
+
+pub struct BlockDeviceProvider;
+
+impl BlockDeviceProvider {
+    /// Walks /sys/block and builds disk -> partition -> holder trees.
+    /// Devices stacked on top of others (dm, md) only appear as children.
+    pub fn scan(disks: &[DiskInfo]) -> Vec<BlockDevice> {
+        let Ok(entries) = fs::read_dir("/sys/block") else {
+            return Vec::new();
+        };
+        let mut names: Vec<String> = entries
+            .flatten()
+            .map(|e| e.file_name().to_string_lossy().to_string())
+            .filter(|n| !n.starts_with("loop") && !n.starts_with("ram"))
+            .collect();
+        names.sort();
+
+        names
+            .iter()
+            .filter(|name| !Self::has_slaves(name))
+            .map(|name| Self::read_device(name, None, disks, 0))
+            .collect()
+    }
+
+    fn sys_path(name: &str, parent: Option<&str>) -> std::path::PathBuf {
+        match parent {
+            // Partitions live inside their disk's directory
+            Some(parent) => Path::new("/sys/block").join(parent).join(name),
+            None => Path::new("/sys/block").join(name),
+        }
+    }
+
+    fn read_trimmed(path: &Path) -> Option<String> {
+        let value = fs::read_to_string(path).ok()?;
+        let value = value.trim();
+        (!value.is_empty()).then(|| value.to_string())
+    }
+
+    /// dm and md devices list the devices they are built on in "slaves"
+    fn has_slaves(name: &str) -> bool {
+        fs::read_dir(Path::new("/sys/block").join(name).join("slaves"))
+            .map(|mut e| e.next().is_some())
+            .unwrap_or(false)
+    }
+
+    fn read_device(
+        name: &str,
+        parent: Option<&str>,
+        disks: &[DiskInfo],
+        depth: usize,
+    ) -> BlockDevice {
+        let path = Self::sys_path(name, parent);
+
+        // "size" is always in 512-byte sectors
+        let size = Self::read_trimmed(&path.join("size"))
+            .and_then(|s| s.parse::<u64>().ok())
+            .unwrap_or(0)
+            * 512;
+
+        let dm_uuid = Self::read_trimmed(&path.join("dm/uuid")).unwrap_or_default();
+        let mapper_name = Self::read_trimmed(&path.join("dm/name"));
+        let kind = if parent.is_some() {
+            BlockDeviceKind::Partition
+        } else if dm_uuid.starts_with("CRYPT-") {
+            BlockDeviceKind::Crypt
+        } else if dm_uuid.starts_with("LVM-") {
+            BlockDeviceKind::Lvm
+        } else if mapper_name.is_some() {
+            BlockDeviceKind::DeviceMapper
+        } else if name.starts_with("md") {
+            BlockDeviceKind::Raid
+        } else {
+            BlockDeviceKind::Disk
+        };
+
+        let (model, media, removable) = if kind == BlockDeviceKind::Disk {
+            let model = Self::read_trimmed(&path.join("device/model"));
+            let rotational = Self::read_trimmed(&path.join("queue/rotational"));
+            let media = if name.starts_with("nvme") {
+                MediaType::Nvme
+            } else {
+                match rotational.as_deref() {
+                    Some("0") => MediaType::Ssd,
+                    Some("1") => MediaType::Hdd,
+                    _ => MediaType::Unknown,
+                }
+            };
+            let removable = Self::read_trimmed(&path.join("removable")).as_deref() == Some("1");
+            (model, media, removable)
+        } else {
+            (None, MediaType::Unknown, false)
+        };
+
+        let mountpoint = Self::find_mountpoint(name, mapper_name.as_deref(), disks);
+
+        let mut children = Vec::new();
+        // Guard against cycles in odd sysfs setups
+        if depth < 8 {
+            if kind == BlockDeviceKind::Disk || kind == BlockDeviceKind::Raid {
+                children.extend(
+                    Self::list_partitions(&path)
+                        .iter()
+                        .map(|part| Self::read_device(part, Some(name), disks, depth + 1)),
+                );
+            }
+            children.extend(
+                Self::list_dir(&path.join("holders"))
+                    .iter()
+                    .map(|holder| Self::read_device(holder, None, disks, depth + 1)),
+            );
+        }
+
+        BlockDevice {
+            name: name.to_string(),
+            mapper_name,
+            kind,
+            model,
+            size,
+            media,
+            removable,
+            mountpoint,
+            children,
+        }
+    }
+
+    fn list_dir(path: &Path) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(path)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .map(|e| e.file_name().to_string_lossy().to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+        names.sort();
+        names
+    }
+
+    /// Partition directories are the ones carrying a "partition" file
+    fn list_partitions(disk_path: &Path) -> Vec<String> {
+        Self::list_dir(disk_path)
+            .into_iter()
+            .filter(|n| disk_path.join(n).join("partition").exists())
+            .collect()
+    }
+
+    fn find_mountpoint(
+        name: &str,
+        mapper_name: Option<&str>,
+        disks: &[DiskInfo],
+    ) -> Option<String> {
+        let kernel_dev = format!("/dev/{}", name);
+        let mapper_dev = mapper_name.map(|m| format!("/dev/mapper/{}", m));
+
+        let mut mounts: Vec<&str> = disks
+            .iter()
+            .filter(|d| d.device == kernel_dev || Some(&d.device) == mapper_dev.as_ref())
+            .map(|d| d.mount_point.as_str())
+            .collect();
+        if mounts.is_empty() {
+            return None;
+        }
+        // btrfs subvolumes mount the same device several times
+        mounts.sort();
+        Some(mounts.join(", "))
+    }
+}
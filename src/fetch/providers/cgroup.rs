@@ -0,0 +1,221 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+
+/// Where the memory and CPU controllers of our cgroup live
+enum Layout {
+    /// cgroup v2: one directory with every controller
+    Unified(PathBuf),
+    /// cgroup v1: one hierarchy per controller
+    Legacy {
+        memory: Option<PathBuf>,
+        cpu: Option<PathBuf>,
+        cpuacct: Option<PathBuf>,
+    },
+}
+
+/// Memory and CPU limits of the cgroup this process runs in, with usage
+/// measured against them. Everything is None when no limit applies.
+pub struct CgroupLimits {
+    layout: Option<Layout>,
+    /// Effective memory limit in bytes (tightest among ancestors), below host RAM
+    pub memory_limit: Option<u64>,
+    /// Memory charged to the cgroup, minus reclaimable file cache
+    pub memory_used: Option<u64>,
+    /// CPU quota expressed in CPUs, e.g. 1.5
+    pub cpu_limit: Option<f64>,
+    /// CPU time used by the cgroup as a percentage of the quota
+    pub cpu_usage: Option<f32>,
+    last_cpu_sample: Option<(Instant, u64)>,
+}
+
+impl CgroupLimits {
+    pub fn new(host_memory: u64, host_cpus: usize) -> Self {
+        let mut limits = Self {
+            layout: Self::detect_layout(),
+            memory_limit: None,
+            memory_used: None,
+            cpu_limit: None,
+            cpu_usage: None,
+            last_cpu_sample: None,
+        };
+        limits.refresh(host_memory, host_cpus);
+        limits
+    }
+
+    /// Re-reads limits and usage. Limits at or above the host's RAM or CPU
+    /// count don't restrict anything and are ignored.
+    pub fn refresh(&mut self, host_memory: u64, host_cpus: usize) {
+        let Some(layout) = &self.layout else {
+            return;
+        };
+
+        let (memory_limit, memory_used, cpu_limit, cpu_usage_ns) = match layout {
+            Layout::Unified(dir) => (
+                Self::v2_memory_limit(dir),
+                Self::read_u64(&dir.join("memory.current")).map(|current| {
+                    current.saturating_sub(Self::inactive_file(dir, "inactive_file"))
+                }),
+                Self::ancestors(dir)
+                    .filter_map(|d| Self::v2_cpu_limit(&d))
+                    .reduce(f64::min),
+                // usage_usec in cpu.stat, converted to ns
+                Self::read_key(&dir.join("cpu.stat"), "usage_usec").map(|us| us * 1000),
+            ),
+            Layout::Legacy {
+                memory,
+                cpu,
+                cpuacct,
+            } => (
+                // hierarchical_memory_limit already folds in the parents' limits
+                memory.as_ref().and_then(|d| {
+                    Self::read_u64(&d.join("memory.limit_in_bytes"))
+                        .into_iter()
+                        .chain(Self::read_key(
+                            &d.join("memory.stat"),
+                            "hierarchical_memory_limit",
+                        ))
+                        .min()
+                }),
+                memory.as_ref().and_then(|d| {
+                    Self::read_u64(&d.join("memory.usage_in_bytes")).map(|usage| {
+                        usage.saturating_sub(Self::inactive_file(d, "total_inactive_file"))
+                    })
+                }),
+                cpu.as_ref().and_then(|d| Self::v1_cpu_limit(d)),
+                cpuacct
+                    .as_ref()
+                    .and_then(|d| Self::read_u64(&d.join("cpuacct.usage"))),
+            ),
+        };
+
+        // v1 reports "unlimited" as a huge page-aligned number
+        self.memory_limit = memory_limit.filter(|&limit| limit < host_memory);
+        self.memory_used = self.memory_limit.and(memory_used);
+        let cpu_limit = cpu_limit.filter(|&cpus| cpus < host_cpus as f64);
+        self.cpu_limit = cpu_limit;
+
+        let now = Instant::now();
+        self.cpu_usage = match (cpu_limit, cpu_usage_ns, self.last_cpu_sample) {
+            (Some(cpus), Some(usage), Some((last_time, last_usage))) => {
+                let elapsed_ns = now.duration_since(last_time).as_nanos() as f64;
+                let used = usage.saturating_sub(last_usage) as f64;
+                (elapsed_ns > 0.0).then(|| (used / elapsed_ns / cpus * 100.0).min(100.0) as f32)
+            }
+            _ => None,
+        };
+        self.last_cpu_sample = cpu_usage_ns.map(|usage| (now, usage));
+    }
+
+    pub fn memory_limited(&self) -> bool {
+        self.memory_limit.is_some()
+    }
+
+    /// Only once there are two samples of the cgroup's CPU time does usage
+    /// replace the host figure
+    pub fn cpu_limited(&self) -> bool {
+        self.cpu_limit.is_some() && self.cpu_usage.is_some()
+    }
+
+    /// Parses /proc/self/cgroup: "0::/path" on v2, "4:memory:/path" on v1
+    fn detect_layout() -> Option<Layout> {
+        let content = fs::read_to_string("/proc/self/cgroup").ok()?;
+        let root = Path::new(CGROUP_ROOT);
+
+        if root.join("cgroup.controllers").exists() {
+            let path = content
+                .lines()
+                .find_map(|l| l.strip_prefix("0::"))
+                .unwrap_or("/");
+            return Some(Layout::Unified(Self::resolve(root, path)));
+        }
+
+        let controller_path = |name: &str| {
+            content.lines().find_map(|line| {
+                let mut parts = line.splitn(3, ':');
+                let (_, controllers, path) = (parts.next()?, parts.next()?, parts.next()?);
+                controllers
+                    .split(',')
+                    .any(|c| c == name)
+                    .then(|| (controllers.to_string(), path.to_string()))
+            })
+        };
+        let memory =
+            controller_path("memory").map(|(mount, path)| Self::resolve(&root.join(mount), &path));
+        // Usually co-mounted as "cpu,cpuacct", with "cpu" and "cpuacct" symlinks
+        let cpu =
+            controller_path("cpu").map(|(mount, path)| Self::resolve(&root.join(mount), &path));
+        let cpuacct =
+            controller_path("cpuacct").map(|(mount, path)| Self::resolve(&root.join(mount), &path));
+        Some(Layout::Legacy {
+            memory,
+            cpu,
+            cpuacct,
+        })
+    }
+
+    /// Inside a cgroup namespace the mount already is our cgroup, so the
+    /// path from /proc/self/cgroup doesn't exist below it
+    fn resolve(mount: &Path, path: &str) -> PathBuf {
+        let full = mount.join(path.trim_start_matches('/'));
+        if full.exists() {
+            full
+        } else {
+            mount.to_path_buf()
+        }
+    }
+
+    /// The cgroup directory and its parents up to the hierarchy root
+    fn ancestors(dir: &Path) -> impl Iterator<Item = PathBuf> + '_ {
+        dir.ancestors()
+            .take_while(|d| d.starts_with(CGROUP_ROOT))
+            .map(Path::to_path_buf)
+    }
+
+    /// A parent's memory.max caps its children too, so take the smallest
+    fn v2_memory_limit(dir: &Path) -> Option<u64> {
+        Self::ancestors(dir)
+            .filter_map(|d| Self::read_u64(&d.join("memory.max")))
+            .min()
+    }
+
+    /// cpu.max is "<quota> <period>" or "max <period>"
+    fn v2_cpu_limit(dir: &Path) -> Option<f64> {
+        let content = fs::read_to_string(dir.join("cpu.max")).ok()?;
+        let mut parts = content.split_whitespace();
+        let quota: f64 = parts.next()?.parse().ok()?;
+        let period: f64 = parts.next()?.parse().ok()?;
+        (period > 0.0).then_some(quota / period)
+    }
+
+    /// cfs_quota_us is -1 when unlimited
+    fn v1_cpu_limit(dir: &Path) -> Option<f64> {
+        let quota: i64 = fs::read_to_string(dir.join("cpu.cfs_quota_us"))
+            .ok()?
+            .trim()
+            .parse()
+            .ok()?;
+        let period = Self::read_u64(&dir.join("cpu.cfs_period_us"))?;
+        (quota > 0 && period > 0).then(|| quota as f64 / period as f64)
+    }
+
+    /// Page cache the kernel can drop at will; `docker stats` leaves it out too
+    fn inactive_file(dir: &Path, key: &str) -> u64 {
+        Self::read_key(&dir.join("memory.stat"), key).unwrap_or(0)
+    }
+
+    /// Single number files; "max" (unlimited) parses to None
+    fn read_u64(path: &Path) -> Option<u64> {
+        fs::read_to_string(path).ok()?.trim().parse().ok()
+    }
+
+    /// "key value" files like memory.stat and cpu.stat
+    fn read_key(path: &Path, key: &str) -> Option<u64> {
+        fs::read_to_string(path).ok()?.lines().find_map(|line| {
+            let (k, v) = line.split_once(' ')?;
+            (k == key).then(|| v.trim().parse().ok())?
+        })
+    }
+}
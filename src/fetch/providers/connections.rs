@@ -0,0 +1,320 @@
+use std::collections::HashMap;
+use std::fs;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::time::{Duration, Instant};
+use sysinfo::{Pid, System};
+
+/// Walking every /proc/<pid>/fd is the expensive part, so rescans are throttled
+const RESCAN_INTERVAL: Duration = Duration::from_secs(1);
+
+/// __SO_ACCEPTCON in /proc/net/unix flags: the socket is listening
+const UNIX_ACCEPTCON: u32 = 0x0001_0000;
+
+#[derive(Clone, Copy, PartialEq, PartialOrd, Eq, Ord)]
+pub enum Protocol {
+    Tcp,
+    Tcp6,
+    Udp,
+    Udp6,
+    Unix,
+}
+
+impl Protocol {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Tcp => "tcp",
+            Self::Tcp6 => "tcp6",
+            Self::Udp => "udp",
+            Self::Udp6 => "udp6",
+            Self::Unix => "unix",
+        }
+    }
+
+    fn is_udp(&self) -> bool {
+        matches!(self, Self::Udp | Self::Udp6)
+    }
+}
+
+/// Socket states, named like `ss` shows them
+#[derive(Clone, Copy, PartialEq)]
+pub enum SocketState {
+    Established,
+    SynSent,
+    SynRecv,
+    FinWait1,
+    FinWait2,
+    TimeWait,
+    /// TCP_CLOSE; for UDP this is an unconnected (bound) socket
+    Unconnected,
+    CloseWait,
+    LastAck,
+    Listen,
+    Closing,
+    Unknown,
+}
+
+impl SocketState {
+    /// States the Connections view cycles through with its filter key
+    pub const FILTERS: [SocketState; 5] = [
+        Self::Established,
+        Self::Listen,
+        Self::TimeWait,
+        Self::CloseWait,
+        Self::Unconnected,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Established => "ESTAB",
+            Self::SynSent => "SYN-SENT",
+            Self::SynRecv => "SYN-RECV",
+            Self::FinWait1 => "FIN-WAIT-1",
+            Self::FinWait2 => "FIN-WAIT-2",
+            Self::TimeWait => "TIME-WAIT",
+            Self::Unconnected => "UNCONN",
+            Self::CloseWait => "CLOSE-WAIT",
+            Self::LastAck => "LAST-ACK",
+            Self::Listen => "LISTEN",
+            Self::Closing => "CLOSING",
+            Self::Unknown => "?",
+        }
+    }
+
+    /// TCP state codes from include/net/tcp_states.h
+    fn from_tcp(code: u8) -> Self {
+        match code {
+            0x01 => Self::Established,
+            0x02 => Self::SynSent,
+            0x03 => Self::SynRecv,
+            0x04 => Self::FinWait1,
+            0x05 => Self::FinWait2,
+            0x06 => Self::TimeWait,
+            0x07 => Self::Unconnected,
+            0x08 => Self::CloseWait,
+            0x09 => Self::LastAck,
+            0x0A => Self::Listen,
+            0x0B => Self::Closing,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+pub struct Connection {
+    pub protocol: Protocol,
+    /// "127.0.0.1:631", "[::]:22" or a unix socket path
+    pub local: String,
+    pub remote: String,
+    pub state: SocketState,
+    pub inode: u64,
+    /// None when the socket belongs to a process we can't inspect
+    pub pid: Option<u32>,
+    pub process: Option<String>,
+}
+
+impl Connection {
+    /// Like `ss -l`: TCP listeners plus bound, unconnected UDP sockets
+    pub fn is_listening(&self) -> bool {
+        self.state == SocketState::Listen
+            || (self.protocol.is_udp() && self.state == SocketState::Unconnected)
+    }
+}
+
+/// Socket table from /proc/net, with owners resolved through /proc/<pid>/fd.
+#[derive(Default)]
+pub struct ConnectionProvider {
+    pub connections: Vec<Connection>,
+    last_scan: Option<Instant>,
+}
+
+impl ConnectionProvider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rescans the socket tables. Process names come from `sys`, which should
+    /// have fresh processes; PIDs missing there fall back to /proc/<pid>/comm.
+    pub fn refresh(&mut self, sys: &System) {
+        if self
+            .last_scan
+            .is_some_and(|t| t.elapsed() < RESCAN_INTERVAL)
+        {
+            return;
+        }
+        self.last_scan = Some(Instant::now());
+
+        let mut connections = Vec::new();
+        for (file, protocol) in [
+            ("/proc/net/tcp", Protocol::Tcp),
+            ("/proc/net/tcp6", Protocol::Tcp6),
+            ("/proc/net/udp", Protocol::Udp),
+            ("/proc/net/udp6", Protocol::Udp6),
+        ] {
+            connections.extend(Self::read_inet(file, protocol));
+        }
+        connections.extend(Self::read_unix());
+
+        let owners = Self::socket_owners();
+        for conn in &mut connections {
+            if let Some(&pid) = owners.get(&conn.inode) {
+                conn.pid = Some(pid);
+                conn.process = sys
+                    .process(Pid::from_u32(pid))
+                    .map(|p| p.name().to_string_lossy().to_string())
+                    .or_else(|| {
+                        fs::read_to_string(format!("/proc/{}/comm", pid))
+                            .ok()
+                            .map(|s| s.trim().to_string())
+                    });
+            }
+        }
+
+        connections.sort_by(|a, b| {
+            a.protocol
+                .cmp(&b.protocol)
+                .then_with(|| a.local.cmp(&b.local))
+        });
+        self.connections = connections;
+    }
+
+    /// Parses /proc/net/{tcp,tcp6,udp,udp6}:
+    ///   sl  local_address rem_address   st tx_queue:rx_queue tr:tm->when retrnsmt  uid  timeout inode
+    ///    0: 0100007F:0277 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 23456
+    fn read_inet(path: &str, protocol: Protocol) -> Vec<Connection> {
+        let Ok(content) = fs::read_to_string(path) else {
+            return Vec::new();
+        };
+
+        content
+            .lines()
+            .skip(1)
+            .filter_map(|line| {
+                let fields: Vec<&str> = line.split_whitespace().collect();
+                if fields.len() < 10 {
+                    return None;
+                }
+                let local = Self::parse_socket_addr(fields[1])?;
+                let remote = Self::parse_socket_addr(fields[2])?;
+                let state = SocketState::from_tcp(u8::from_str_radix(fields[3], 16).ok()?);
+                let remote = if remote.ip().is_unspecified() && remote.port() == 0 {
+                    "*".to_string()
+                } else {
+                    remote.to_string()
+                };
+
+                Some(Connection {
+                    protocol,
+                    local: local.to_string(),
+                    remote,
+                    state,
+                    inode: fields[9].parse().ok()?,
+                    pid: None,
+                    process: None,
+                })
+            })
+            .collect()
+    }
+
+    /// "0100007F:0277" -> 127.0.0.1:631. The address is a hex dump of the
+    /// in-kernel words (host byte order per 32-bit word), the port is plain hex.
+    fn parse_socket_addr(field: &str) -> Option<SocketAddr> {
+        let (addr, port) = field.split_once(':')?;
+        let port = u16::from_str_radix(port, 16).ok()?;
+
+        let mut bytes = Vec::with_capacity(16);
+        for i in (0..addr.len()).step_by(8) {
+            let word = u32::from_str_radix(addr.get(i..i + 8)?, 16).ok()?;
+            bytes.extend_from_slice(&word.to_ne_bytes());
+        }
+
+        let ip = match bytes.len() {
+            4 => IpAddr::V4(Ipv4Addr::new(bytes[0], bytes[1], bytes[2], bytes[3])),
+            16 => {
+                let octets: [u8; 16] = bytes.try_into().ok()?;
+                let v6 = Ipv6Addr::from(octets);
+                // Show v4-mapped addresses (::ffff:1.2.3.4) the way ss does
+                v6.to_ipv4_mapped().map_or(IpAddr::V6(v6), IpAddr::V4)
+            }
+            _ => return None,
+        };
+        Some(SocketAddr::new(ip, port))
+    }
+
+    /// Parses /proc/net/unix:
+    /// Num       RefCount Protocol Flags    Type St Inode Path
+    /// 00000000: 00000002 00000000 00010000 0001 01 23456 /run/user/1000/bus
+    fn read_unix() -> Vec<Connection> {
+        let Ok(content) = fs::read_to_string("/proc/net/unix") else {
+            return Vec::new();
+        };
+
+        content
+            .lines()
+            .skip(1)
+            .filter_map(|line| {
+                let fields: Vec<&str> = line.split_whitespace().collect();
+                if fields.len() < 7 {
+                    return None;
+                }
+                let flags = u32::from_str_radix(fields[3], 16).ok()?;
+                // St is the socket_state enum: 1 unconnected, 3 connected
+                let state = if flags & UNIX_ACCEPTCON != 0 {
+                    SocketState::Listen
+                } else {
+                    match fields[5] {
+                        "03" => SocketState::Established,
+                        "01" => SocketState::Unconnected,
+                        _ => SocketState::Unknown,
+                    }
+                };
+
+                Some(Connection {
+                    protocol: Protocol::Unix,
+                    // Abstract sockets start with '@', unnamed ones have no path
+                    local: fields.get(7).map_or("*".to_string(), |p| p.to_string()),
+                    remote: "*".to_string(),
+                    state,
+                    inode: fields[6].parse().ok()?,
+                    pid: None,
+                    process: None,
+                })
+            })
+            .collect()
+    }
+
+    /// Maps socket inodes to the first PID holding them via the
+    /// "socket:[inode]" links in /proc/<pid>/fd. Other users' processes are
+    /// unreadable without root and simply stay unresolved.
+    fn socket_owners() -> HashMap<u64, u32> {
+        let mut owners = HashMap::new();
+        let Ok(entries) = fs::read_dir("/proc") else {
+            return owners;
+        };
+
+        for entry in entries.flatten() {
+            let Some(pid) = entry
+                .file_name()
+                .to_str()
+                .and_then(|s| s.parse::<u32>().ok())
+            else {
+                continue;
+            };
+            let Ok(fds) = fs::read_dir(entry.path().join("fd")) else {
+                continue;
+            };
+            for fd in fds.flatten() {
+                let Ok(target) = fs::read_link(fd.path()) else {
+                    continue;
+                };
+                if let Some(inode) = target
+                    .to_str()
+                    .and_then(|t| t.strip_prefix("socket:["))
+                    .and_then(|t| t.strip_suffix(']'))
+                    .and_then(|t| t.parse::<u64>().ok())
+                {
+                    owners.entry(inode).or_insert(pid);
+                }
+            }
+        }
+        owners
+    }
+}
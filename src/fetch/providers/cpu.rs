@@ -1,24 +1,432 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 use sysinfo::System;
 
+const CPU_PATH: &str = "/sys/devices/system/cpu";
+
+/// How often the current frequency is re-read; it's one file per thread
+const FREQ_INTERVAL: Duration = Duration::from_secs(1);
+
+/// One kind of cache, e.g. "L2 Unified, 1.25 MiB, 6 instances"
+pub struct CacheInfo {
+    pub level: u8,
+    /// "Data", "Instruction" or "Unified"
+    pub kind: String,
+    pub size_kib: u64,
+    /// Number of separate caches of this kind (per core, per cluster, ...)
+    pub count: usize,
+}
+
+impl CacheInfo {
+    /// "L1d", "L1i", "L2", "L3"
+    pub fn label(&self) -> String {
+        match self.kind.as_str() {
+            "Data" => format!("L{}d", self.level),
+            "Instruction" => format!("L{}i", self.level),
+            _ => format!("L{}", self.level),
+        }
+    }
+}
+
 pub struct CpuInfo {
     pub models: Vec<String>,
+    /// Logical CPUs
     pub cores: usize,
+    pub sockets: usize,
+    pub physical_cores: usize,
+    /// Performance and efficiency core counts on hybrid parts
+    pub hybrid: Option<(usize, usize)>,
+    pub freq_min_mhz: Option<u64>,
+    pub freq_max_mhz: Option<u64>,
+    /// Highest current frequency among all threads
+    pub freq_current_mhz: Option<u64>,
+    pub caches: Vec<CacheInfo>,
+    /// e.g. "powersave (intel_pstate)"
+    pub governor: Option<String>,
+    pub microcode: Option<String>,
+    last_freq_read: Option<Instant>,
 }
 
 impl CpuInfo {
     pub fn new(sys: &System) -> Self {
-        let mut cpu_models: Vec<String> =
-            sys.cpus().iter().map(|c| c.brand().to_string()).collect();
+        let cpuinfo = fs::read_to_string("/proc/cpuinfo").unwrap_or_default();
+
+        let mut cpu_models: Vec<String> = sys
+            .cpus()
+            .iter()
+            .map(|c| c.brand().trim().to_string())
+            .filter(|brand| !brand.is_empty())
+            .collect();
         cpu_models.sort();
         cpu_models.dedup();
 
+        // ARM kernels usually leave the brand empty; name the cores instead
+        if cpu_models.is_empty()
+            && let Some(arm) = Self::arm_models(&cpuinfo)
+        {
+            cpu_models.push(arm);
+        }
         if cpu_models.is_empty() {
             cpu_models.push("Unknown CPU".to_string());
         }
 
-        Self {
+        let cpu_dirs = Self::cpu_dirs();
+        let (sockets, physical_cores) = Self::topology(&cpu_dirs);
+        let cpufreq =
+            |file: &str| Self::read_trimmed(&Path::new(CPU_PATH).join("cpu0/cpufreq").join(file));
+        let khz_to_mhz = |v: String| v.parse::<u64>().ok().map(|khz| khz / 1000);
+
+        let governor = cpufreq("scaling_governor").map(|gov| match cpufreq("scaling_driver") {
+            Some(driver) => format!("{} ({})", gov, driver),
+            None => gov,
+        });
+        let microcode = cpuinfo.lines().find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            (key.trim() == "microcode").then(|| value.trim().to_string())
+        });
+
+        let mut info = Self {
             models: cpu_models,
             cores: sys.cpus().len(),
+            sockets,
+            physical_cores,
+            hybrid: Self::hybrid_split(),
+            freq_min_mhz: cpufreq("cpuinfo_min_freq").and_then(khz_to_mhz),
+            freq_max_mhz: cpufreq("cpuinfo_max_freq").and_then(khz_to_mhz),
+            freq_current_mhz: None,
+            caches: Self::caches(&cpu_dirs),
+            governor,
+            microcode,
+            last_freq_read: None,
+        };
+        info.refresh_frequency();
+        info
+    }
+
+    /// Re-reads the current frequency, at most once per second
+    pub fn refresh_frequency(&mut self) {
+        if self
+            .last_freq_read
+            .is_some_and(|last| last.elapsed() < FREQ_INTERVAL)
+        {
+            return;
         }
+        self.last_freq_read = Some(Instant::now());
+
+        let from_sysfs = Self::cpu_dirs()
+            .iter()
+            .filter_map(|dir| Self::read_trimmed(&dir.join("cpufreq/scaling_cur_freq")))
+            .filter_map(|khz| khz.parse::<u64>().ok())
+            .max()
+            .map(|khz| khz / 1000);
+        // VMs often lack cpufreq but /proc/cpuinfo still has "cpu MHz"
+        self.freq_current_mhz = from_sysfs.or_else(|| {
+            fs::read_to_string("/proc/cpuinfo")
+                .ok()?
+                .lines()
+                .filter_map(|line| {
+                    let (key, value) = line.split_once(':')?;
+                    (key.trim() == "cpu MHz").then(|| value.trim().parse::<f64>().ok())?
+                })
+                .reduce(f64::max)
+                .map(|mhz| mhz as u64)
+        });
+    }
+
+    /// First model name, for places with room for one line
+    pub fn name(&self) -> &str {
+        self.models.first().map_or("Unknown CPU", |m| m.as_str())
+    }
+
+    /// "Intel(R) Core(TM) i7-12700H (6P + 8E, 20 threads) @ 4.70 GHz"
+    pub fn get_formatted(&self) -> String {
+        let mut out = self.name().to_string();
+        match self.hybrid {
+            Some((p, e)) => out.push_str(&format!(" ({}P + {}E, {} threads)", p, e, self.cores)),
+            None => out.push_str(&format!(" ({})", self.cores)),
+        }
+        if let Some(max) = self.freq_max_mhz.or(self.freq_current_mhz) {
+            out.push_str(&format!(" @ {:.2} GHz", max as f64 / 1000.0));
+        }
+        out
+    }
+
+    /// "1 socket, 14 cores, 20 threads, microcode 0x4121"
+    pub fn get_formatted_topology(&self) -> String {
+        let plural =
+            |n: usize, word: &str| format!("{} {}{}", n, word, if n == 1 { "" } else { "s" });
+        let mut out = format!(
+            "{}, {}, {}",
+            plural(self.sockets, "socket"),
+            plural(self.physical_cores, "core"),
+            plural(self.cores, "thread")
+        );
+        if let Some(microcode) = &self.microcode {
+            out.push_str(&format!(", microcode {}", microcode));
+        }
+        out
+    }
+
+    /// "3.10 GHz (0.40 - 4.70 GHz), powersave (intel_pstate)"
+    pub fn get_formatted_frequency(&self) -> String {
+        let ghz = |mhz: u64| format!("{:.2}", mhz as f64 / 1000.0);
+        let mut out = match self.freq_current_mhz {
+            Some(current) => format!("{} GHz", ghz(current)),
+            None => "Unknown".to_string(),
+        };
+        if let (Some(min), Some(max)) = (self.freq_min_mhz, self.freq_max_mhz) {
+            out.push_str(&format!(" ({} - {} GHz)", ghz(min), ghz(max)));
+        }
+        if let Some(governor) = &self.governor {
+            out.push_str(&format!(", {}", governor));
+        }
+        out
+    }
+
+    /// "L1d 48 KiB x 6, L1i 32 KiB x 6, L2 1.25 MiB x 6, L3 24 MiB"
+    pub fn get_formatted_caches(&self) -> String {
+        if self.caches.is_empty() {
+            return "Unknown".to_string();
+        }
+        self.caches
+            .iter()
+            .map(|cache| {
+                let size = if cache.size_kib >= 1024 {
+                    format!("{} MiB", cache.size_kib as f64 / 1024.0)
+                } else {
+                    format!("{} KiB", cache.size_kib)
+                };
+                if cache.count > 1 {
+                    format!("{} {} x {}", cache.label(), size, cache.count)
+                } else {
+                    format!("{} {}", cache.label(), size)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// cpu0, cpu1, ... (not cpufreq, cpuidle, ...)
+    fn cpu_dirs() -> Vec<PathBuf> {
+        let Ok(entries) = fs::read_dir(CPU_PATH) else {
+            return Vec::new();
+        };
+        let mut dirs: Vec<(u32, PathBuf)> = entries
+            .flatten()
+            .filter_map(|entry| {
+                let name = entry.file_name().to_string_lossy().to_string();
+                let index = name.strip_prefix("cpu")?.parse().ok()?;
+                Some((index, entry.path()))
+            })
+            .collect();
+        dirs.sort();
+        dirs.into_iter().map(|(_, path)| path).collect()
+    }
+
+    /// Distinct packages and distinct (package, core) pairs
+    fn topology(cpu_dirs: &[PathBuf]) -> (usize, usize) {
+        let mut packages = BTreeSet::new();
+        let mut cores = BTreeSet::new();
+        for dir in cpu_dirs {
+            let package = Self::read_trimmed(&dir.join("topology/physical_package_id"));
+            let core = Self::read_trimmed(&dir.join("topology/core_id"));
+            if let (Some(package), Some(core)) = (package, core) {
+                cores.insert((package.clone(), core));
+                packages.insert(package);
+            }
+        }
+        (packages.len().max(1), cores.len().max(1))
+    }
+
+    /// Intel hybrid parts register two PMUs listing their CPUs; P-cores are
+    /// hyperthreaded, so cores are counted by distinct core_id
+    fn hybrid_split() -> Option<(usize, usize)> {
+        let count_cores = |pmu: &str| -> Option<usize> {
+            let list = Self::read_trimmed(&Path::new("/sys/devices").join(pmu).join("cpus"))?;
+            let cores: BTreeSet<String> = Self::parse_cpu_list(&list)
+                .iter()
+                .filter_map(|cpu| {
+                    Self::read_trimmed(
+                        &Path::new(CPU_PATH).join(format!("cpu{}/topology/core_id", cpu)),
+                    )
+                })
+                .collect();
+            Some(cores.len())
+        };
+        let p_cores = count_cores("cpu_core")?;
+        let e_cores = count_cores("cpu_atom")?;
+        (p_cores > 0 && e_cores > 0).then_some((p_cores, e_cores))
+    }
+
+    /// Caches shared between threads show up under each of them;
+    /// shared_cpu_list tells the instances apart
+    fn caches(cpu_dirs: &[PathBuf]) -> Vec<CacheInfo> {
+        let mut seen = BTreeSet::new();
+        // (level, type, size) -> instances
+        let mut kinds: BTreeMap<(u8, String, u64), usize> = BTreeMap::new();
+        for dir in cpu_dirs {
+            let Ok(entries) = fs::read_dir(dir.join("cache")) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let path = entry.path();
+                let read = |file: &str| Self::read_trimmed(&path.join(file));
+                let (Some(level), Some(kind), Some(size), Some(shared)) = (
+                    read("level").and_then(|l| l.parse::<u8>().ok()),
+                    read("type"),
+                    read("size").and_then(|s| Self::parse_size_kib(&s)),
+                    read("shared_cpu_list"),
+                ) else {
+                    continue;
+                };
+                if seen.insert((level, kind.clone(), shared)) {
+                    *kinds.entry((level, kind, size)).or_default() += 1;
+                }
+            }
+        }
+
+        let mut caches: Vec<CacheInfo> = kinds
+            .into_iter()
+            .map(|((level, kind, size_kib), count)| CacheInfo {
+                level,
+                kind,
+                size_kib,
+                count,
+            })
+            .collect();
+        // L1d before L1i, like lscpu
+        caches.sort_by_key(|c| (c.level, c.kind != "Data", std::cmp::Reverse(c.size_kib)));
+        caches
+    }
+
+    /// "48K", "1280K", "30M" -> KiB
+    fn parse_size_kib(size: &str) -> Option<u64> {
+        if let Some(kib) = size.strip_suffix('K') {
+            kib.parse().ok()
+        } else if let Some(mib) = size.strip_suffix('M') {
+            mib.parse::<u64>().ok().map(|m| m * 1024)
+        } else {
+            size.parse::<u64>().ok().map(|bytes| bytes / 1024)
+        }
+    }
+
+    /// "0-3,8,10-11" -> [0, 1, 2, 3, 8, 10, 11]
+    fn parse_cpu_list(list: &str) -> Vec<u32> {
+        list.split(',')
+            .filter_map(|range| match range.split_once('-') {
+                Some((start, end)) => Some((start.parse().ok()?..=end.parse().ok()?).collect()),
+                None => range.parse().ok().map(|cpu| vec![cpu]),
+            })
+            .flatten()
+            .collect()
+    }
+
+    /// "4x Cortex-A76 + 4x Cortex-A55" from the implementer/part pairs in
+    /// /proc/cpuinfo, in order of first appearance
+    fn arm_models(cpuinfo: &str) -> Option<String> {
+        let mut implementer = None;
+        let mut parts: Vec<(String, usize)> = Vec::new();
+        for line in cpuinfo.lines() {
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let value = value.trim();
+            match key.trim() {
+                "CPU implementer" => {
+                    implementer = u32::from_str_radix(value.trim_start_matches("0x"), 16).ok()
+                }
+                "CPU part" => {
+                    let Some(part) = u32::from_str_radix(value.trim_start_matches("0x"), 16).ok()
+                    else {
+                        continue;
+                    };
+                    let name = Self::arm_part_name(implementer.unwrap_or(0), part);
+                    match parts.iter_mut().find(|(n, _)| *n == name) {
+                        Some((_, count)) => *count += 1,
+                        None => parts.push((name, 1)),
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        match parts.as_slice() {
+            [] => None,
+            [(name, _)] => Some(name.clone()),
+            _ => Some(
+                parts
+                    .iter()
+                    .map(|(name, count)| format!("{}x {}", count, name))
+                    .collect::<Vec<_>>()
+                    .join(" + "),
+            ),
+        }
+    }
+
+    /// Names for the common implementer/part IDs (as listed by lscpu)
+    fn arm_part_name(implementer: u32, part: u32) -> String {
+        let known = match (implementer, part) {
+            (0x41, 0xd03) => Some("Cortex-A53"),
+            (0x41, 0xd04) => Some("Cortex-A35"),
+            (0x41, 0xd05) => Some("Cortex-A55"),
+            (0x41, 0xd07) => Some("Cortex-A57"),
+            (0x41, 0xd08) => Some("Cortex-A72"),
+            (0x41, 0xd09) => Some("Cortex-A73"),
+            (0x41, 0xd0a) => Some("Cortex-A75"),
+            (0x41, 0xd0b) => Some("Cortex-A76"),
+            (0x41, 0xd0c) => Some("Neoverse-N1"),
+            (0x41, 0xd0d) => Some("Cortex-A77"),
+            (0x41, 0xd40) => Some("Neoverse-V1"),
+            (0x41, 0xd41) => Some("Cortex-A78"),
+            (0x41, 0xd44) => Some("Cortex-X1"),
+            (0x41, 0xd46) => Some("Cortex-A510"),
+            (0x41, 0xd47) => Some("Cortex-A710"),
+            (0x41, 0xd48) => Some("Cortex-X2"),
+            (0x41, 0xd49) => Some("Neoverse-N2"),
+            (0x41, 0xd4b) => Some("Cortex-A78C"),
+            (0x41, 0xd4d) => Some("Cortex-A715"),
+            (0x41, 0xd4e) => Some("Cortex-X3"),
+            (0x41, 0xd4f) => Some("Neoverse-V2"),
+            (0x41, 0xd80) => Some("Cortex-A520"),
+            (0x41, 0xd81) => Some("Cortex-A720"),
+            (0x41, 0xd82) => Some("Cortex-X4"),
+            (0x48, 0xd01) => Some("TaiShan-v110"),
+            (0x4e, 0x004) => Some("Carmel"),
+            (0x51, 0x800) | (0x51, 0x802) | (0x51, 0x804) => Some("Kryo Gold"),
+            (0x51, 0x801) | (0x51, 0x803) | (0x51, 0x805) => Some("Kryo Silver"),
+            (0x51, 0x001) => Some("Oryon"),
+            (0x61, 0x022) | (0x61, 0x024) | (0x61, 0x028) => Some("Icestorm"),
+            (0x61, 0x023) | (0x61, 0x025) | (0x61, 0x029) => Some("Firestorm"),
+            (0x61, 0x032) => Some("Blizzard"),
+            (0x61, 0x033) => Some("Avalanche"),
+            (0xc0, 0xac3) => Some("Ampere-1"),
+            _ => None,
+        };
+        if let Some(name) = known {
+            return name.to_string();
+        }
+        let vendor = match implementer {
+            0x41 => "ARM",
+            0x42 => "Broadcom",
+            0x43 => "Cavium",
+            0x48 => "HiSilicon",
+            0x4e => "NVIDIA",
+            0x51 => "Qualcomm",
+            0x53 => "Samsung",
+            0x56 => "Marvell",
+            0x61 => "Apple",
+            0xc0 => "Ampere",
+            _ => "Unknown",
+        };
+        format!("{} part 0x{:03x}", vendor, part)
+    }
+
+    fn read_trimmed(path: &Path) -> Option<String> {
+        fs::read_to_string(path)
+            .ok()
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
     }
 }
@@ -0,0 +1,160 @@
+use std::collections::HashSet;
+use std::fs;
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+/// Directories nested deeper than this are counted as unreadable
+const MAX_DEPTH: usize = 4096;
+/// Room for MAX_DEPTH levels of recursion; the 2 MiB default isn't
+const SCAN_STACK_SIZE: usize = 32 * 1024 * 1024;
+
+pub struct DirNode {
+    pub name: String,
+    /// Disk usage of the whole subtree in bytes (allocated blocks, like du/ncdu)
+    pub size: u64,
+    /// Disk usage of the regular files directly inside this directory
+    pub own_files_size: u64,
+    pub own_files: u64,
+    /// Sorted by size, largest first
+    pub children: Vec<DirNode>,
+}
+
+/// Counters shared with the scanning thread so the UI can show progress
+#[derive(Default)]
+pub struct ScanProgress {
+    pub bytes: AtomicU64,
+    pub entries: AtomicU64,
+    pub skipped: AtomicU64,
+    cancelled: AtomicBool,
+}
+
+/// A directory size scan running on a background thread.
+pub struct DirScan {
+    pub root: PathBuf,
+    pub progress: Arc<ScanProgress>,
+    pub result: Option<DirNode>,
+    /// Why the scan couldn't run, e.g. the root is not readable
+    pub error: Option<String>,
+    rx: mpsc::Receiver<Result<DirNode, String>>,
+}
+
+/// State shared by the whole recursive walk
+struct Walk<'a> {
+    device: u64,
+    progress: &'a ScanProgress,
+    /// (dev, inode) of files with several links, so each is counted once
+    hard_links: HashSet<(u64, u64)>,
+}
+
+impl DirScan {
+    /// Starts scanning `root`, never crossing into other filesystems.
+    pub fn start(root: &Path) -> Self {
+        let progress = Arc::new(ScanProgress::default());
+        let (tx, rx) = mpsc::channel();
+
+        let thread_root = root.to_path_buf();
+        let thread_progress = Arc::clone(&progress);
+        // If the thread can't be spawned, poll() sees the channel disconnect
+        let _ = thread::Builder::new()
+            .stack_size(SCAN_STACK_SIZE)
+            .spawn(move || {
+                let result = fs::symlink_metadata(&thread_root)
+                    .map(|meta| {
+                        let name = thread_root.display().to_string();
+                        let mut walk = Walk {
+                            device: meta.dev(),
+                            progress: &thread_progress,
+                            hard_links: HashSet::new(),
+                        };
+                        Self::scan_dir(&thread_root, name, 0, &mut walk)
+                    })
+                    .map_err(|e| e.to_string());
+                let _ = tx.send(result);
+            });
+
+        Self {
+            root: root.to_path_buf(),
+            progress,
+            result: None,
+            error: None,
+            rx,
+        }
+    }
+
+    /// Picks up the result once the thread is done. Returns true while still scanning.
+    pub fn poll(&mut self) -> bool {
+        if self.result.is_none() && self.error.is_none() {
+            match self.rx.try_recv() {
+                Ok(Ok(node)) => self.result = Some(node),
+                Ok(Err(e)) => self.error = Some(e),
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.error = Some("scan stopped unexpectedly".to_string())
+                }
+                Err(mpsc::TryRecvError::Empty) => {}
+            }
+        }
+        self.result.is_none() && self.error.is_none()
+    }
+
+    pub fn cancel(&self) {
+        self.progress.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    fn scan_dir(path: &Path, name: String, depth: usize, walk: &mut Walk) -> DirNode {
+        let progress = walk.progress;
+        let mut node = DirNode {
+            name,
+            size: 0,
+            own_files_size: 0,
+            own_files: 0,
+            children: Vec::new(),
+        };
+
+        if progress.cancelled.load(Ordering::Relaxed) {
+            return node;
+        }
+
+        let entries = (depth < MAX_DEPTH).then(|| fs::read_dir(path));
+        let Some(Ok(entries)) = entries else {
+            progress.skipped.fetch_add(1, Ordering::Relaxed);
+            return node;
+        };
+
+        for entry in entries.flatten() {
+            // DirEntry::metadata doesn't follow symlinks, so links never leave the tree
+            let Ok(meta) = entry.metadata() else {
+                progress.skipped.fetch_add(1, Ordering::Relaxed);
+                continue;
+            };
+            progress.entries.fetch_add(1, Ordering::Relaxed);
+
+            if meta.is_dir() {
+                // Stay on one filesystem: mount points of other devices are skipped
+                if meta.dev() != walk.device {
+                    continue;
+                }
+                let child_name = entry.file_name().to_string_lossy().to_string();
+                let child = Self::scan_dir(&entry.path(), child_name, depth + 1, walk);
+                node.size += child.size;
+                node.children.push(child);
+            } else if meta.nlink() > 1 && !walk.hard_links.insert((meta.dev(), meta.ino())) {
+                // Another link to a file already counted; du counts it once too
+                continue;
+            } else {
+                // st_blocks is always in 512-byte units
+                let usage = meta.blocks() * 512;
+                progress.bytes.fetch_add(usage, Ordering::Relaxed);
+                node.own_files_size += usage;
+                node.own_files += 1;
+            }
+        }
+
+        node.size += node.own_files_size;
+        node.children.sort_by_key(|c| std::cmp::Reverse(c.size));
+        node
+    }
+}
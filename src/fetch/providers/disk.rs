@@ -1,41 +1,143 @@
+use crate::config::DiskConfig;
+use std::ffi::CString;
+use std::os::unix::ffi::OsStrExt;
+use std::path::Path;
 use sysinfo::Disks;
 
+/// Filesystems that never hold user data. sysinfo already drops the most
+/// common ones (proc, sysfs, tmpfs, ...), these are the ones that slip through.
+const PSEUDO_FILESYSTEMS: [&str; 12] = [
+    "overlay",
+    "efivarfs",
+    "bpf",
+    "tracefs",
+    "debugfs",
+    "configfs",
+    "securityfs",
+    "ramfs",
+    "binfmt_misc",
+    "fusectl",
+    "fuse.portal",
+    "fuse.gvfsd-fuse",
+];
+
+pub struct DiskInfo {
+    pub mount_point: String,
+    pub device: String,
+    pub fs_type: String,
+    pub total: u64,
+    pub available: u64,
+    pub inodes_total: u64,
+    pub inodes_free: u64,
+    pub read_only: bool,
+    pub removable: bool,
+}
+
+impl DiskInfo {
+    pub fn used(&self) -> u64 {
+        self.total.saturating_sub(self.available)
+    }
+
+    pub fn usage_ratio(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            self.used() as f64 / self.total as f64
+        }
+    }
+
+    /// Some filesystems (btrfs, vfat) don't have a fixed inode table
+    pub fn inode_ratio(&self) -> Option<f64> {
+        if self.inodes_total == 0 {
+            return None;
+        }
+        let used = self.inodes_total.saturating_sub(self.inodes_free);
+        Some(used as f64 / self.inodes_total as f64)
+    }
+
+    /// "12.34 GiB / 100.00 GiB (12%) - ext4"
+    pub fn summary(&self) -> String {
+        let gib = 1024.0 * 1024.0 * 1024.0;
+        let mut out = format!(
+            "{:.2} GiB / {:.2} GiB ({:.0}%) - {}",
+            self.used() as f64 / gib,
+            self.total as f64 / gib,
+            self.usage_ratio() * 100.0,
+            self.fs_type
+        );
+        if self.read_only {
+            out.push_str(" [ro]");
+        }
+        out
+    }
+}
+
 pub struct DiskProvider;
 
 impl DiskProvider {
-    pub fn get_disk_usage(disk_handle: &Disks) -> String {
-        let mut usage_info = Vec::new();
-        let target_mounts = ["/", "/home"];
-
-        for disk in disk_handle {
-            let mount_point = disk.mount_point();
-            if target_mounts
-                .iter()
-                .any(|&m| mount_point == std::path::Path::new(m))
-            {
-                let total_gb = disk.total_space() as f64 / 1024.0 / 1024.0 / 1024.0;
-                let available_gb = disk.available_space() as f64 / 1024.0 / 1024.0 / 1024.0;
-                let used_gb = total_gb - available_gb;
-                let percent = (used_gb / total_gb) * 100.0;
-                let fs = disk.file_system().to_string_lossy();
-
-                let mount_name = if mount_point == std::path::Path::new("/") {
-                    "Root"
-                } else {
-                    "Home"
-                };
-
-                usage_info.push(format!(
-                    "{}: {:.2} GiB / {:.2} GiB ({:.0}%) - {}",
-                    mount_name, used_gb, total_gb, percent, fs
-                ));
-            }
+    pub fn get_disks(disk_handle: &Disks, config: &DiskConfig) -> Vec<DiskInfo> {
+        let mut disks: Vec<DiskInfo> = disk_handle
+            .iter()
+            .filter(|disk| {
+                let mount_point = disk.mount_point().to_string_lossy();
+                let fs_type = disk.file_system().to_string_lossy();
+                Self::is_visible(&mount_point, &fs_type, config)
+            })
+            .map(|disk| {
+                let (inodes_total, inodes_free) = Self::inode_stats(disk.mount_point());
+                DiskInfo {
+                    mount_point: disk.mount_point().to_string_lossy().to_string(),
+                    device: disk.name().to_string_lossy().to_string(),
+                    fs_type: disk.file_system().to_string_lossy().to_string(),
+                    total: disk.total_space(),
+                    available: disk.available_space(),
+                    inodes_total,
+                    inodes_free,
+                    read_only: disk.is_read_only(),
+                    removable: disk.is_removable(),
+                }
+            })
+            .collect();
+
+        disks.sort_by(|a, b| a.mount_point.cmp(&b.mount_point));
+        disks
+    }
+
+    fn is_visible(mount_point: &str, fs_type: &str, config: &DiskConfig) -> bool {
+        let matches = |patterns: &[String]| patterns.iter().any(|p| Self::matches(p, mount_point));
+
+        if !config.include.is_empty() {
+            return matches(&config.include);
         }
+        if matches(&config.exclude) {
+            return false;
+        }
+        config.show_pseudo
+            || !(PSEUDO_FILESYSTEMS.contains(&fs_type)
+                || mount_point == "/dev"
+                || mount_point.starts_with("/dev/")
+                || mount_point.starts_with("/snap/")
+                || mount_point.starts_with("/var/lib/docker/overlay2"))
+    }
 
-        if usage_info.is_empty() {
-            "Unknown".to_string()
-        } else {
-            usage_info.join(" | ")
+    /// Exact mount point, or a prefix when the pattern ends with '*' (e.g. "/media/*")
+    fn matches(pattern: &str, mount_point: &str) -> bool {
+        match pattern.strip_suffix('*') {
+            Some(prefix) => mount_point.starts_with(prefix),
+            None => pattern.trim_end_matches('/') == mount_point.trim_end_matches('/'),
+        }
+    }
+
+    /// Returns (total, free) inodes via statvfs, (0, 0) if unavailable.
+    fn inode_stats(mount_point: &Path) -> (u64, u64) {
+        let Ok(path) = CString::new(mount_point.as_os_str().as_bytes()) else {
+            return (0, 0);
+        };
+        let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+        // SAFETY: `path` is a valid NUL-terminated string and `stat` is a valid out pointer
+        if unsafe { libc::statvfs(path.as_ptr(), &mut stat) } != 0 {
+            return (0, 0);
         }
+        (stat.f_files as u64, stat.f_ffree as u64)
     }
 }
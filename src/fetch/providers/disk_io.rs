@@ -0,0 +1,179 @@
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::path::Path;
+use std::time::Instant;
+
+/// Number of samples kept for the sparklines
+pub const IO_HISTORY_LEN: usize = 60;
+
+/// /proc/diskstats always counts in 512-byte sectors, whatever the device's sector size
+const SECTOR_SIZE: u64 = 512;
+
+/// Raw cumulative counters for one device
+#[derive(Clone, Copy)]
+struct DiskStatSample {
+    reads: u64,
+    sectors_read: u64,
+    read_ticks_ms: u64,
+    writes: u64,
+    sectors_written: u64,
+    write_ticks_ms: u64,
+    io_ticks_ms: u64,
+}
+
+pub struct DiskIoInfo {
+    pub device: String,
+    pub read_bytes_per_sec: f64,
+    pub write_bytes_per_sec: f64,
+    pub read_iops: f64,
+    pub write_iops: f64,
+    /// Average time a request completed in the last interval took, queueing included
+    pub read_await_ms: f64,
+    pub write_await_ms: f64,
+    /// Share of wall time the device had I/O in flight (0-100)
+    pub busy_percent: f64,
+    /// Combined read+write throughput, oldest first
+    pub history: VecDeque<f64>,
+}
+
+/// Turns successive /proc/diskstats samples into per-device rates.
+pub struct DiskIoProvider {
+    previous: HashMap<String, DiskStatSample>,
+    last_sample: Option<Instant>,
+    pub devices: Vec<DiskIoInfo>,
+}
+
+impl Default for DiskIoProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DiskIoProvider {
+    pub fn new() -> Self {
+        let mut provider = Self {
+            previous: HashMap::new(),
+            last_sample: None,
+            devices: Vec::new(),
+        };
+        // Prime the counters so the first refresh already has a baseline
+        provider.refresh();
+        provider
+    }
+
+    pub fn refresh(&mut self) {
+        let Ok(content) = fs::read_to_string("/proc/diskstats") else {
+            return;
+        };
+        let now = Instant::now();
+        let elapsed = self
+            .last_sample
+            .map(|t| now.duration_since(t).as_secs_f64())
+            .unwrap_or(0.0);
+
+        let mut current = HashMap::new();
+        let mut devices = Vec::new();
+
+        for line in content.lines() {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 13 {
+                continue;
+            }
+            let name = fields[2];
+            if !Self::is_whole_disk(name) {
+                continue;
+            }
+
+            let field = |i: usize| fields[i].parse::<u64>().unwrap_or(0);
+            let sample = DiskStatSample {
+                reads: field(3),
+                sectors_read: field(5),
+                read_ticks_ms: field(6),
+                writes: field(7),
+                sectors_written: field(9),
+                write_ticks_ms: field(10),
+                io_ticks_ms: field(12),
+            };
+
+            let mut info = self.take_device(name).unwrap_or_else(|| DiskIoInfo {
+                device: name.to_string(),
+                read_bytes_per_sec: 0.0,
+                write_bytes_per_sec: 0.0,
+                read_iops: 0.0,
+                write_iops: 0.0,
+                read_await_ms: 0.0,
+                write_await_ms: 0.0,
+                busy_percent: 0.0,
+                history: VecDeque::with_capacity(IO_HISTORY_LEN),
+            });
+
+            if let Some(prev) = self.previous.get(name)
+                && elapsed > 0.0
+            {
+                // Counters can wrap or reset (device re-attached), saturating_sub keeps that at 0
+                let rate = |cur: u64, old: u64| cur.saturating_sub(old) as f64 / elapsed;
+                info.read_bytes_per_sec =
+                    rate(sample.sectors_read, prev.sectors_read) * SECTOR_SIZE as f64;
+                info.write_bytes_per_sec =
+                    rate(sample.sectors_written, prev.sectors_written) * SECTOR_SIZE as f64;
+                info.read_iops = rate(sample.reads, prev.reads);
+                info.write_iops = rate(sample.writes, prev.writes);
+                // Time spent on the requests completed since the last sample, per request
+                let await_ms = |ticks: u64, old_ticks: u64, ops: u64, old_ops: u64| {
+                    let completed = ops.saturating_sub(old_ops);
+                    if completed == 0 {
+                        0.0
+                    } else {
+                        ticks.saturating_sub(old_ticks) as f64 / completed as f64
+                    }
+                };
+                info.read_await_ms = await_ms(
+                    sample.read_ticks_ms,
+                    prev.read_ticks_ms,
+                    sample.reads,
+                    prev.reads,
+                );
+                info.write_await_ms = await_ms(
+                    sample.write_ticks_ms,
+                    prev.write_ticks_ms,
+                    sample.writes,
+                    prev.writes,
+                );
+                info.busy_percent = (rate(sample.io_ticks_ms, prev.io_ticks_ms) / 10.0).min(100.0);
+
+                if info.history.len() == IO_HISTORY_LEN {
+                    info.history.pop_front();
+                }
+                info.history
+                    .push_back(info.read_bytes_per_sec + info.write_bytes_per_sec);
+            }
+
+            current.insert(name.to_string(), sample);
+            devices.push(info);
+        }
+
+        self.previous = current;
+        self.last_sample = Some(now);
+        self.devices = devices;
+    }
+
+    /// Moves a device's previous info out so its history carries over
+    fn take_device(&mut self, name: &str) -> Option<DiskIoInfo> {
+        let idx = self.devices.iter().position(|d| d.device == name)?;
+        Some(self.devices.swap_remove(idx))
+    }
+
+    /// Partitions don't get their own /sys/block entry; loop and ram devices are noise
+    fn is_whole_disk(name: &str) -> bool {
+        !name.starts_with("loop")
+            && !name.starts_with("ram")
+            && Path::new("/sys/block").join(name).exists()
+    }
+
+    /// The busiest device, used by the dashboard gauge
+    pub fn busiest(&self) -> Option<&DiskIoInfo> {
+        self.devices
+            .iter()
+            .max_by(|a, b| a.busy_percent.total_cmp(&b.busy_percent))
+    }
+}
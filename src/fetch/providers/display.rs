@@ -1,68 +1,596 @@
+use std::fs;
+use std::path::Path;
 use std::process::Command;
 
-pub struct DisplayInfo;
+#[derive(Clone, Copy, PartialEq)]
+pub struct DisplayMode {
+    pub width: u32,
+    pub height: u32,
+    pub refresh: f32,
+}
+
+impl DisplayMode {
+    /// Parses "1920x1080" style resolution strings (anything after the height is ignored).
+    fn parse_resolution(s: &str) -> Option<(u32, u32)> {
+        let (w, rest) = s.split_once('x')?;
+        let h: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+        Some((w.trim().parse().ok()?, h.parse().ok()?))
+    }
+}
+
+pub struct MonitorInfo {
+    /// Connector name as used by the compositor, e.g. "eDP-1" or "HDMI-A-1"
+    pub connector: String,
+    pub manufacturer: Option<String>,
+    pub model: Option<String>,
+    /// Physical size in millimeters (width, height)
+    pub physical_size: Option<(u32, u32)>,
+    pub preferred_mode: Option<DisplayMode>,
+    pub current_mode: Option<DisplayMode>,
+    pub scale: Option<f32>,
+    pub built_in: bool,
+}
+
+impl MonitorInfo {
+    fn new(connector: &str) -> Self {
+        Self {
+            connector: connector.to_string(),
+            manufacturer: None,
+            model: None,
+            physical_size: None,
+            preferred_mode: None,
+            current_mode: None,
+            scale: None,
+            built_in: ["eDP", "LVDS", "DSI"]
+                .iter()
+                .any(|p| connector.starts_with(p)),
+        }
+    }
+
+    /// Diagonal size in inches, derived from the physical size.
+    pub fn diagonal_inches(&self) -> Option<f32> {
+        let (w, h) = self.physical_size?;
+        if w == 0 || h == 0 {
+            return None;
+        }
+        Some(((w * w + h * h) as f32).sqrt() / 25.4)
+    }
+
+    /// fastfetch-like one line summary:
+    /// "Dell U2720Q (DP-1): 3840x2160 @ 60 Hz, scale 1.5, 27\" [External]"
+    pub fn summary(&self) -> String {
+        let name = match (&self.manufacturer, &self.model) {
+            (_, Some(model)) => format!("{} ({})", model, self.connector),
+            (Some(vendor), None) => format!("{} ({})", vendor, self.connector),
+            (None, None) => self.connector.clone(),
+        };
+
+        let mut out = name;
+        if let Some(mode) = self.current_mode.or(self.preferred_mode) {
+            out.push_str(&format!(": {}x{}", mode.width, mode.height));
+            if mode.refresh > 0.0 {
+                out.push_str(&format!(" @ {:.0} Hz", mode.refresh));
+            }
+        }
+        if let Some(scale) = self.scale
+            && (scale - 1.0).abs() > f32::EPSILON
+        {
+            out.push_str(&format!(", scale {}", scale));
+        }
+        if let Some(inches) = self.diagonal_inches() {
+            out.push_str(&format!(", {:.0}\"", inches));
+        }
+        out.push_str(if self.built_in {
+            " [Built-in]"
+        } else {
+            " [External]"
+        });
+        out
+    }
+}
+
+pub struct DisplayInfo {
+    pub monitors: Vec<MonitorInfo>,
+}
+
+impl Default for DisplayInfo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl DisplayInfo {
-    pub fn new() -> String {
-        // Try generic Wayland/X11 tools or specific DE tools
-
-        // 1. Try wlr-randr (Wayland generic)
-        if let Ok(output) = Command::new("wlr-randr").output() {
-            if output.status.success() {
-                // Parsing logic would go here, but wlr-randr output is complex.
-                // Simplified for now.
+    pub fn new() -> Self {
+        // 1. Enumerate connected connectors natively and decode their EDID
+        let mut monitors = Self::read_drm();
+
+        // 2. Ask the compositor for the current mode/scale.
+        // wlr-randr covers wlroots compositors, kscreen-doctor KDE,
+        // xrandr X11 (and XWayland as a last resort).
+        let runtime = Self::read_wlr_randr()
+            .or_else(Self::read_kscreen_doctor)
+            .or_else(Self::read_xrandr)
+            .unwrap_or_default();
+
+        let drm_found = !monitors.is_empty();
+        let drm_names: Vec<&str> = monitors.iter().map(|m| m.connector.as_str()).collect();
+        let runtime_names: Vec<&str> = runtime.iter().map(|m| m.connector.as_str()).collect();
+        let pairs = Self::pair_connectors(&drm_names, &runtime_names);
+        for (rt, pair) in runtime.into_iter().zip(pairs) {
+            if let Some(m) = pair.map(|i| &mut monitors[i]) {
+                m.current_mode = rt.current_mode.or(m.current_mode);
+                m.scale = rt.scale.or(m.scale);
+                if m.model.is_none() {
+                    m.model = rt.model;
+                }
+                if m.physical_size.is_none() {
+                    m.physical_size = rt.physical_size;
+                }
+            } else if !drm_found {
+                // Not visible under /sys/class/drm (e.g. proprietary drivers or VMs)
+                monitors.push(rt);
+            }
+        }
+
+        Self { monitors }
+    }
+
+    /// Connector type and index, comparable across naming schemes:
+    /// "HDMI-A-1", "HDMI1" -> ("hdmi", Some(1)); "DisplayPort-0" -> ("dp", Some(0));
+    /// "eDP" -> ("edp", None)
+    fn connector_key(connector: &str) -> (String, Option<u32>) {
+        let name = connector.to_lowercase().replace(['-', '_'], "");
+        let kind = name.trim_end_matches(|c: char| c.is_ascii_digit());
+        let index = name[kind.len()..].parse().ok();
+        let kind = match kind {
+            "displayport" => "dp",
+            "hdmia" | "hdmib" => "hdmi",
+            other => other,
+        };
+        (kind.to_string(), index)
+    }
+
+    /// For each runtime connector, the DRM connector it is. Where the runtime
+    /// indices all exist in DRM they're compared directly; otherwise the names
+    /// follow another scheme (amdgpu's xrandr counts from 0, "eDP" has no index)
+    /// and connectors of one type are paired in order.
+    fn pair_connectors(drm: &[&str], runtime: &[&str]) -> Vec<Option<usize>> {
+        let drm_keys: Vec<_> = drm.iter().map(|c| Self::connector_key(c)).collect();
+        let runtime_keys: Vec<_> = runtime.iter().map(|c| Self::connector_key(c)).collect();
+        // Positions of one connector type, ordered by index
+        let of_kind = |keys: &[(String, Option<u32>)], kind: &str| {
+            let mut positions: Vec<usize> =
+                (0..keys.len()).filter(|&i| keys[i].0 == kind).collect();
+            positions.sort_by_key(|&i| keys[i].1);
+            positions
+        };
+
+        runtime_keys
+            .iter()
+            .enumerate()
+            .map(|(i, (kind, index))| {
+                let drm_of_kind = of_kind(&drm_keys, kind);
+                let runtime_of_kind = of_kind(&runtime_keys, kind);
+                let same_scheme = runtime_of_kind.iter().all(|&r| {
+                    runtime_keys[r].1.is_some()
+                        && drm_of_kind
+                            .iter()
+                            .any(|&d| drm_keys[d].1 == runtime_keys[r].1)
+                });
+                if same_scheme {
+                    drm_of_kind.into_iter().find(|&d| drm_keys[d].1 == *index)
+                } else {
+                    let order = runtime_of_kind.iter().position(|&r| r == i)?;
+                    drm_of_kind.get(order).copied()
+                }
+            })
+            .collect()
+    }
+
+    fn read_drm() -> Vec<MonitorInfo> {
+        let mut monitors = Vec::new();
+
+        if let Ok(entries) = fs::read_dir("/sys/class/drm/") {
+            let mut paths: Vec<_> = entries.flatten().map(|e| e.path()).collect();
+            paths.sort();
+
+            for path in paths {
+                let filename = path.file_name().unwrap_or_default().to_string_lossy();
+                // Connectors look like "card1-eDP-1"; "card1" itself is the GPU
+                let Some((card, connector)) = filename.split_once('-') else {
+                    continue;
+                };
+                if !card.starts_with("card") {
+                    continue;
+                }
+
+                let status = fs::read_to_string(path.join("status")).unwrap_or_default();
+                if status.trim() != "connected" {
+                    continue;
+                }
+
+                let mut monitor = MonitorInfo::new(connector);
+                if let Ok(edid) = fs::read(path.join("edid")) {
+                    Self::apply_edid(&mut monitor, &edid);
+                }
+                if monitor.preferred_mode.is_none() {
+                    monitor.preferred_mode = Self::read_first_mode(&path);
+                }
+                monitors.push(monitor);
+            }
+        }
+
+        monitors
+    }
+
+    /// The kernel lists the preferred mode first in the connector's "modes" file.
+    fn read_first_mode(path: &Path) -> Option<DisplayMode> {
+        let modes = fs::read_to_string(path.join("modes")).ok()?;
+        let (width, height) = DisplayMode::parse_resolution(modes.lines().next()?)?;
+        Some(DisplayMode {
+            width,
+            height,
+            refresh: 0.0,
+        })
+    }
+
+    fn apply_edid(monitor: &mut MonitorInfo, edid: &[u8]) {
+        const HEADER: [u8; 8] = [0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00];
+        if edid.len() < 128 || edid[..8] != HEADER {
+            return;
+        }
+
+        // Manufacturer ID: three 5-bit letters packed big-endian in bytes 8-9
+        let id = u16::from_be_bytes([edid[8], edid[9]]);
+        let letters: String = [(id >> 10) & 0x1f, (id >> 5) & 0x1f, id & 0x1f]
+            .iter()
+            .map(|&c| (b'A' - 1 + c as u8) as char)
+            .collect();
+        let product_code = u16::from_le_bytes([edid[10], edid[11]]);
+        monitor.manufacturer = Some(Self::vendor_name(&letters).unwrap_or(letters));
+
+        // Bytes 21-22: maximum image size in centimeters
+        if edid[21] > 0 && edid[22] > 0 {
+            monitor.physical_size = Some((edid[21] as u32 * 10, edid[22] as u32 * 10));
+        }
+
+        // Four 18-byte descriptors starting at byte 54
+        for (i, desc) in edid[54..126].chunks_exact(18).enumerate() {
+            let pixel_clock = u16::from_le_bytes([desc[0], desc[1]]);
+            if pixel_clock != 0 {
+                // Detailed timing descriptor. The first one is the preferred mode.
+                if i == 0 {
+                    Self::apply_detailed_timing(monitor, desc, pixel_clock);
+                }
+            } else if desc[3] == 0xfc {
+                // Monitor name descriptor
+                let name: String = desc[5..18]
+                    .iter()
+                    .take_while(|&&b| b != 0x0a)
+                    .map(|&b| b as char)
+                    .collect();
+                let name = name.trim();
+                if !name.is_empty() {
+                    monitor.model = Some(name.to_string());
+                }
             }
         }
 
-        // 2. Try xrandr (X11 generic) - often works on Wayland via XWayland but might report virtual screens
-        // Let's rely on a simpler approach if possible: parsing /sys/class/drm or using a command like `kscreen-doctor` for KDE since the user uses KDE.
-
-        // KDE Plasma specific
-        if let Ok(output) = Command::new("kscreen-doctor").arg("-o").output() {
-            if output.status.success() {
-                let stdout = String::from_utf8_lossy(&output.stdout);
-                // Output example:
-                // Output: 1 eDP-1 enabled connected priority 1 pos 0,0 size 1920x1080@144Hz scale 1.0
-                for line in stdout.lines() {
-                    if line.contains("enabled") && line.contains("connected") {
-                        // Extract resolution and refresh rate
-                        // naive parsing
-                        let parts: Vec<&str> = line.split_whitespace().collect();
-                        for part in parts {
-                            if part.contains('x') && part.contains('@') {
-                                return part.to_string(); // Found "1920x1080@144Hz"
-                            }
-                            // Sometimes they are separate "1920x1080 60Hz"
-                        }
-                    }
+        // Laptop panels usually don't have a name descriptor; fall back to the product code
+        if monitor.model.is_none() {
+            monitor.model = Some(format!(
+                "{} 0x{:04X}",
+                monitor.manufacturer.as_deref().unwrap_or("Unknown"),
+                product_code
+            ));
+        }
+    }
+
+    fn apply_detailed_timing(monitor: &mut MonitorInfo, desc: &[u8], pixel_clock: u16) {
+        let h_active = desc[2] as u32 | ((desc[4] as u32 & 0xf0) << 4);
+        let h_blank = desc[3] as u32 | ((desc[4] as u32 & 0x0f) << 8);
+        let v_active = desc[5] as u32 | ((desc[7] as u32 & 0xf0) << 4);
+        let v_blank = desc[6] as u32 | ((desc[7] as u32 & 0x0f) << 8);
+
+        let total = (h_active + h_blank) * (v_active + v_blank);
+        let refresh = if total > 0 {
+            (pixel_clock as f64 * 10_000.0 / total as f64) as f32
+        } else {
+            0.0
+        };
+
+        monitor.preferred_mode = Some(DisplayMode {
+            width: h_active,
+            height: v_active,
+            refresh,
+        });
+
+        // The DTD carries the image size in millimeters, which is more precise
+        let h_mm = desc[12] as u32 | ((desc[14] as u32 & 0xf0) << 4);
+        let v_mm = desc[13] as u32 | ((desc[14] as u32 & 0x0f) << 8);
+        if h_mm > 0 && v_mm > 0 {
+            monitor.physical_size = Some((h_mm, v_mm));
+        }
+    }
+
+    fn vendor_name(pnp_id: &str) -> Option<String> {
+        // Small subset of the PNP ID registry covering common panels and monitors
+        let name = match pnp_id {
+            "ACR" => "Acer",
+            "AOC" => "AOC",
+            "APP" => "Apple",
+            "AUO" => "AU Optronics",
+            "AUS" => "ASUS",
+            "BNQ" => "BenQ",
+            "BOE" => "BOE",
+            "CMN" => "Chimei Innolux",
+            "DEL" => "Dell",
+            "GSM" => "LG",
+            "HWP" => "HP",
+            "IVO" => "InfoVision",
+            "LEN" => "Lenovo",
+            "LGD" => "LG Display",
+            "MSI" => "MSI",
+            "PHL" => "Philips",
+            "SAM" => "Samsung",
+            "SDC" => "Samsung Display",
+            "SHP" => "Sharp",
+            "SNY" => "Sony",
+            "VSC" => "ViewSonic",
+            _ => return None,
+        };
+        Some(name.to_string())
+    }
+
+    fn run(cmd: &str, args: &[&str]) -> Option<String> {
+        let output = Command::new(cmd).args(args).output().ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        Some(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+
+    fn read_wlr_randr() -> Option<Vec<MonitorInfo>> {
+        let stdout = Self::run("wlr-randr", &[])?;
+        let monitors = Self::parse_wlr_randr(&stdout);
+        (!monitors.is_empty()).then_some(monitors)
+    }
+
+    /// Output example:
+    /// eDP-1 "Sharp Corporation 0x14F9 (eDP-1)"
+    ///   Physical size: 290x180 mm
+    ///   Enabled: yes
+    ///   Modes:
+    ///     1920x1200 px, 59.950001 Hz (preferred, current)
+    ///   Scale: 1.000000
+    fn parse_wlr_randr(stdout: &str) -> Vec<MonitorInfo> {
+        let mut monitors: Vec<MonitorInfo> = Vec::new();
+        let mut enabled = true;
+
+        for line in stdout.lines() {
+            if !line.starts_with(' ') && !line.trim().is_empty() {
+                if !enabled {
+                    monitors.pop();
+                }
+                enabled = true;
+                let connector = line.split_whitespace().next().unwrap_or_default();
+                monitors.push(MonitorInfo::new(connector));
+                continue;
+            }
+
+            let Some(monitor) = monitors.last_mut() else {
+                continue;
+            };
+            let line = line.trim();
+
+            if let Some(v) = line.strip_prefix("Enabled:") {
+                enabled = v.trim() == "yes";
+            } else if let Some(v) = line.strip_prefix("Model:") {
+                monitor.model = Some(v.trim().to_string());
+            } else if let Some(v) = line.strip_prefix("Physical size:") {
+                monitor.physical_size = DisplayMode::parse_resolution(v.trim());
+            } else if let Some(v) = line.strip_prefix("Scale:") {
+                monitor.scale = v.trim().parse().ok();
+            } else if line.contains(" px,") && line.contains("current") {
+                let (res, rest) = line.split_once(" px,").unwrap_or_default();
+                if let Some((width, height)) = DisplayMode::parse_resolution(res) {
+                    let refresh = rest
+                        .split_whitespace()
+                        .next()
+                        .and_then(|hz| hz.parse().ok())
+                        .unwrap_or(0.0);
+                    monitor.current_mode = Some(DisplayMode {
+                        width,
+                        height,
+                        refresh,
+                    });
                 }
             }
         }
+        if !enabled {
+            monitors.pop();
+        }
+
+        monitors
+    }
 
-        // Fallback: Generic X11/XWayland resolution detection via xrandr
-        if let Ok(output) = Command::new("xrandr").arg("--current").output() {
-            if output.status.success() {
-                let stdout = String::from_utf8_lossy(&output.stdout);
-                // connected primary 1920x1080+0+0
-                for line in stdout.lines() {
-                    if line.contains(" connected") {
-                        // Find the resolution part. usually after "primary" or the device name
-                        // eDP-1 connected primary 1920x1080+0+0 ...
-                        if let Some(res_start) = line.find(|c: char| c.is_numeric()) {
-                            // This is very loose, but catching "1920x1080"
-                            let rest = &line[res_start..];
-                            if let Some(end) = rest.find('+') {
-                                let res = &rest[..end];
-                                // Try to find refresh rate on the next line usually
-                                // But on one line mode it might be: 1920x1080 144.00*+
-                                return res.to_string();
-                            }
-                        }
-                    }
+    fn read_kscreen_doctor() -> Option<Vec<MonitorInfo>> {
+        let stdout = Self::run("kscreen-doctor", &["-o"])?;
+        let monitors = Self::parse_kscreen_doctor(&stdout);
+        (!monitors.is_empty()).then_some(monitors)
+    }
+
+    /// Output example (single or multi-line depending on version):
+    /// Output: 1 eDP-1 enabled connected priority 1 Panel Modes: 0:1920x1080@144*! ... Scale: 1.25
+    fn parse_kscreen_doctor(stdout: &str) -> Vec<MonitorInfo> {
+        let mut monitors: Vec<MonitorInfo> = Vec::new();
+        let mut usable = false;
+        let mut expect_scale = false;
+
+        for token in stdout.split_whitespace() {
+            if token == "Output:" {
+                if !usable {
+                    monitors.pop();
+                }
+                usable = false;
+                monitors.push(MonitorInfo::new(""));
+                continue;
+            }
+
+            let Some(monitor) = monitors.last_mut() else {
+                continue;
+            };
+
+            if monitor.connector.is_empty() && token.parse::<u32>().is_err() {
+                monitor.connector = token.to_string();
+            } else if token == "enabled" {
+                usable = true;
+            } else if token == "disabled" || token == "disconnected" {
+                usable = false;
+            } else if token == "Scale:" {
+                expect_scale = true;
+            } else if expect_scale {
+                monitor.scale = token.parse().ok();
+                expect_scale = false;
+            } else if token.contains('*') && token.contains('@') {
+                // Current mode is marked with '*', e.g. "0:1920x1080@144*!"
+                let mode = token.rsplit(':').next().unwrap_or_default();
+                let (res, hz) = mode.split_once('@').unwrap_or_default();
+                if let Some((width, height)) = DisplayMode::parse_resolution(res) {
+                    monitor.current_mode = Some(DisplayMode {
+                        width,
+                        height,
+                        refresh: hz
+                            .trim_end_matches(['*', '!', 'H', 'z'])
+                            .parse()
+                            .unwrap_or(0.0),
+                    });
+                }
+            } else if token.contains('x') && token.contains('@') && monitor.current_mode.is_none() {
+                // Older versions print the current mode directly, e.g. "1920x1080@144Hz"
+                let (res, hz) = token.split_once('@').unwrap_or_default();
+                if let Some((width, height)) = DisplayMode::parse_resolution(res) {
+                    monitor.current_mode = Some(DisplayMode {
+                        width,
+                        height,
+                        refresh: hz.trim_end_matches(['!', 'H', 'z']).parse().unwrap_or(0.0),
+                    });
+                }
+            }
+        }
+        if !usable {
+            monitors.pop();
+        }
+
+        monitors
+    }
+
+    fn read_xrandr() -> Option<Vec<MonitorInfo>> {
+        let stdout = Self::run("xrandr", &["--current"])?;
+        let monitors = Self::parse_xrandr(&stdout);
+        (!monitors.is_empty()).then_some(monitors)
+    }
+
+    /// Output example:
+    /// eDP-1 connected primary 1920x1080+0+0 (normal left inverted right x axis y axis) 344mm x 194mm
+    ///    1920x1080     60.02*+  60.01    59.97
+    fn parse_xrandr(stdout: &str) -> Vec<MonitorInfo> {
+        let mut monitors: Vec<MonitorInfo> = Vec::new();
+        let mut in_connected = false;
+
+        for line in stdout.lines() {
+            if !line.starts_with(' ') {
+                let parts: Vec<&str> = line.split_whitespace().collect();
+                in_connected = parts.get(1) == Some(&"connected");
+                if !in_connected {
+                    continue;
+                }
+
+                let mut monitor = MonitorInfo::new(parts[0]);
+                // Geometry "WxH+X+Y" is only present for active outputs
+                if let Some(geometry) = parts.iter().find(|p| p.contains('x') && p.contains('+'))
+                    && let Some((width, height)) = DisplayMode::parse_resolution(geometry)
+                {
+                    monitor.current_mode = Some(DisplayMode {
+                        width,
+                        height,
+                        refresh: 0.0,
+                    });
+                }
+                // Physical size "344mm x 194mm"
+                if let Some(i) = parts.iter().position(|p| p.ends_with("mm"))
+                    && let (Some(w), Some(h)) = (parts.get(i), parts.get(i + 2))
+                    && let (Ok(w), Ok(h)) = (
+                        w.trim_end_matches("mm").parse::<u32>(),
+                        h.trim_end_matches("mm").parse::<u32>(),
+                    )
+                    && w > 0
+                    && h > 0
+                {
+                    monitor.physical_size = Some((w, h));
+                }
+                monitors.push(monitor);
+            } else if in_connected && line.contains('*') {
+                // Mode line; the current refresh rate is the one marked with '*'
+                let Some(monitor) = monitors.last_mut() else {
+                    continue;
+                };
+                let mut parts = line.split_whitespace();
+                let res = parts.next().unwrap_or_default();
+                let refresh = parts
+                    .find(|p| p.contains('*'))
+                    .and_then(|p| p.trim_end_matches(['*', '+']).parse().ok())
+                    .unwrap_or(0.0);
+                if let Some((width, height)) = DisplayMode::parse_resolution(res) {
+                    monitor.current_mode = Some(DisplayMode {
+                        width,
+                        height,
+                        refresh,
+                    });
                 }
             }
         }
 
-        "Unknown".to_string()
+        // Connected but inactive outputs have no current mode; skip them
+        monitors.retain(|m| m.current_mode.is_some());
+        monitors
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn connector_key_normalizes_both_naming_schemes() {
+        let key = |c| DisplayInfo::connector_key(c);
+        assert_eq!(key("HDMI-A-1"), ("hdmi".to_string(), Some(1)));
+        assert_eq!(key("HDMI-1"), key("HDMI-A-1"));
+        assert_eq!(key("eDP1"), key("eDP-1"));
+        assert_eq!(key("DisplayPort-0"), ("dp".to_string(), Some(0)));
+        assert_eq!(key("eDP"), ("edp".to_string(), None));
+    }
+
+    #[test]
+    fn xrandr_names_pair_with_drm_connectors() {
+        let drm = ["eDP-1", "DP-1", "DP-2", "HDMI-A-1"];
+        // intel/modesetting: same indices, different spelling
+        assert_eq!(
+            DisplayInfo::pair_connectors(&drm, &["eDP1", "DP2", "HDMI1"]),
+            [Some(0), Some(2), Some(3)]
+        );
+        // amdgpu: 0-based and "eDP" without an index
+        assert_eq!(
+            DisplayInfo::pair_connectors(
+                &drm,
+                &["eDP", "DisplayPort-0", "DisplayPort-1", "HDMI-A-0"]
+            ),
+            [Some(0), Some(1), Some(2), Some(3)]
+        );
+        // Wayland compositors use the DRM names
+        assert_eq!(
+            DisplayInfo::pair_connectors(&drm, &["HDMI-A-1", "eDP-1"]),
+            [Some(3), Some(0)]
+        );
     }
 }
@@ -4,6 +4,12 @@ pub struct GpuInfo {
     pub names: Vec<String>,
 }
 
+impl Default for GpuInfo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl GpuInfo {
     pub fn new() -> Self {
         let mut names = Vec::new();
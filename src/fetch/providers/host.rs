@@ -0,0 +1,132 @@
+use std::fs;
+use std::path::Path;
+
+const DMI_PATH: &str = "/sys/class/dmi/id";
+
+/// Placeholders vendors leave in DMI fields they didn't bother to fill
+const JUNK_VALUES: [&str; 16] = [
+    "to be filled by o.e.m.",
+    "to be filled by oem",
+    "default string",
+    "system product name",
+    "system version",
+    "system manufacturer",
+    "not applicable",
+    "not specified",
+    "not available",
+    "none",
+    "oem",
+    "o.e.m.",
+    "0123456789",
+    "123456789",
+    "type1productconfigid",
+    "x.x",
+];
+
+/// Hardware identity from DMI (x86) or the device tree (ARM boards).
+#[derive(Default)]
+pub struct HostInfo {
+    pub vendor: Option<String>,
+    pub product_name: Option<String>,
+    pub product_version: Option<String>,
+    pub board_vendor: Option<String>,
+    pub board_name: Option<String>,
+    pub bios_vendor: Option<String>,
+    pub bios_version: Option<String>,
+    /// ISO date, converted from DMI's MM/DD/YYYY
+    pub bios_date: Option<String>,
+    pub uefi: bool,
+    /// /proc/device-tree/model, e.g. "Raspberry Pi 4 Model B Rev 1.4"
+    pub device_tree_model: Option<String>,
+}
+
+impl HostInfo {
+    pub fn new() -> Self {
+        let dmi = |field: &str| Self::read_clean(&Path::new(DMI_PATH).join(field));
+
+        Self {
+            vendor: dmi("sys_vendor"),
+            product_name: dmi("product_name"),
+            product_version: dmi("product_version"),
+            board_vendor: dmi("board_vendor"),
+            board_name: dmi("board_name"),
+            bios_vendor: dmi("bios_vendor"),
+            bios_version: dmi("bios_version"),
+            bios_date: dmi("bios_date").map(|d| Self::iso_date(&d)),
+            uefi: Path::new("/sys/firmware/efi").exists(),
+            device_tree_model: Self::read_clean(Path::new("/proc/device-tree/model")),
+        }
+    }
+
+    /// Trimmed file content, None when missing, empty or a placeholder.
+    /// Device tree strings are NUL-terminated.
+    fn read_clean(path: &Path) -> Option<String> {
+        let value = fs::read_to_string(path).ok()?;
+        let value = value.trim_matches(|c: char| c.is_whitespace() || c == '\0');
+        if value.is_empty() || JUNK_VALUES.contains(&value.to_lowercase().as_str()) {
+            return None;
+        }
+        Some(value.to_string())
+    }
+
+    /// "05/01/2023" -> "2023-05-01"; anything else is kept as is
+    fn iso_date(date: &str) -> String {
+        match date.split('/').collect::<Vec<_>>().as_slice() {
+            [month, day, year] if year.len() == 4 => format!("{}-{}-{}", year, month, day),
+            _ => date.to_string(),
+        }
+    }
+
+    /// "ThinkPad T14 Gen 3 (21AH)", "XPS 13 9310", "Raspberry Pi 4 Model B Rev 1.4"
+    pub fn model(&self) -> Option<String> {
+        let Some(name) = &self.product_name else {
+            // Desktop boards often only fill in the board fields
+            return self
+                .board_name
+                .as_ref()
+                .map(|board| match &self.board_vendor {
+                    Some(vendor) => format!("{} {}", vendor, board),
+                    None => board.clone(),
+                })
+                .or_else(|| self.device_tree_model.clone());
+        };
+
+        // Lenovo puts the machine type in product_name and the model in product_version
+        let is_lenovo = self
+            .vendor
+            .as_deref()
+            .is_some_and(|v| v.eq_ignore_ascii_case("lenovo"));
+        let model = match &self.product_version {
+            Some(version) if is_lenovo => {
+                let machine_type: String = name.chars().take(4).collect();
+                format!("{} ({})", version, machine_type)
+            }
+            // Versions like "1.0" add nothing
+            Some(version) if !version.chars().all(|c| c.is_ascii_digit() || c == '.') => {
+                format!("{} ({})", name, version)
+            }
+            _ => name.clone(),
+        };
+        Some(model)
+    }
+
+    pub fn get_formatted_model(&self) -> String {
+        self.model().unwrap_or_else(|| "Unknown".to_string())
+    }
+
+    /// "LENOVO N3MET18W (1.17) (2023-05-01) [UEFI]"
+    pub fn get_formatted_bios(&self) -> String {
+        let Some(version) = &self.bios_version else {
+            return "Unknown".to_string();
+        };
+        let mut out = match &self.bios_vendor {
+            Some(vendor) => format!("{} {}", vendor, version),
+            None => version.clone(),
+        };
+        if let Some(date) = &self.bios_date {
+            out.push_str(&format!(" ({})", date));
+        }
+        out.push_str(if self.uefi { " [UEFI]" } else { " [Legacy]" });
+        out
+    }
+}
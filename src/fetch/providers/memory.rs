@@ -1,20 +1,359 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
 use sysinfo::System;
 
+/// Fields of /proc/meminfo, in bytes
+#[derive(Default)]
+pub struct MemoryBreakdown {
+    pub total: u64,
+    pub free: u64,
+    pub available: u64,
+    pub buffers: u64,
+    /// Page cache plus reclaimable slab, minus shmem (counted in shared), like htop
+    pub cached: u64,
+    pub shared: u64,
+    pub dirty: u64,
+    pub writeback: u64,
+    pub swap_cached: u64,
+    pub huge_pages_total: u64,
+    pub huge_pages_free: u64,
+    pub huge_page_size: u64,
+    /// Transparent huge pages backing anonymous memory
+    pub anon_huge: u64,
+    /// Compressed size of the zswap pool and the uncompressed data in it (5.19+)
+    pub zswap: Option<(u64, u64)>,
+}
+
+impl MemoryBreakdown {
+    fn read() -> Self {
+        let Ok(content) = fs::read_to_string("/proc/meminfo") else {
+            return Self::default();
+        };
+        // "MemTotal:       16318720 kB"; HugePages_* are page counts without a unit
+        let values: HashMap<&str, u64> = content
+            .lines()
+            .filter_map(|line| {
+                let (key, rest) = line.split_once(':')?;
+                let mut parts = rest.split_whitespace();
+                let value: u64 = parts.next()?.parse().ok()?;
+                Some((
+                    key,
+                    if parts.next() == Some("kB") {
+                        value * 1024
+                    } else {
+                        value
+                    },
+                ))
+            })
+            .collect();
+        let get = |key: &str| values.get(key).copied().unwrap_or(0);
+
+        let shared = get("Shmem");
+        Self {
+            total: get("MemTotal"),
+            free: get("MemFree"),
+            available: get("MemAvailable"),
+            buffers: get("Buffers"),
+            cached: (get("Cached") + get("SReclaimable")).saturating_sub(shared),
+            shared,
+            dirty: get("Dirty"),
+            writeback: get("Writeback"),
+            swap_cached: get("SwapCached"),
+            huge_pages_total: get("HugePages_Total"),
+            huge_pages_free: get("HugePages_Free"),
+            huge_page_size: get("Hugepagesize"),
+            anon_huge: get("AnonHugePages"),
+            zswap: values
+                .get("Zswap")
+                .map(|&pool| (pool, values.get("Zswapped").copied().unwrap_or(0))),
+        }
+    }
+
+    /// Memory that is neither free, buffers nor cache (shmem included), as in htop's bar
+    pub fn used(&self) -> u64 {
+        self.total
+            .saturating_sub(self.free + self.buffers + self.cached)
+    }
+}
+
+/// A zram block device; sizes from mm_stat, in bytes
+pub struct ZramDevice {
+    pub name: String,
+    pub disk_size: u64,
+    /// Uncompressed size of the data stored
+    pub orig_data_size: u64,
+    pub compr_data_size: u64,
+    /// RAM actually used, including allocator overhead
+    pub mem_used_total: u64,
+    /// The active algorithm, shown as "[lzo-rle]" in comp_algorithm
+    pub algorithm: Option<String>,
+}
+
+impl ZramDevice {
+    fn scan() -> Vec<Self> {
+        let Ok(entries) = fs::read_dir("/sys/block") else {
+            return Vec::new();
+        };
+        let mut devices: Vec<Self> = entries
+            .flatten()
+            .filter(|e| e.file_name().to_string_lossy().starts_with("zram"))
+            .filter_map(|entry| {
+                let dir = entry.path();
+                // orig_data_size compr_data_size mem_used_total mem_limit ...
+                let stat = fs::read_to_string(dir.join("mm_stat")).ok()?;
+                let fields: Vec<u64> = stat
+                    .split_whitespace()
+                    .filter_map(|f| f.parse().ok())
+                    .collect();
+                let disk_size = fs::read_to_string(dir.join("disksize"))
+                    .ok()?
+                    .trim()
+                    .parse()
+                    .ok()?;
+                // Unconfigured devices have no size
+                if disk_size == 0 {
+                    return None;
+                }
+                let algorithm = fs::read_to_string(dir.join("comp_algorithm"))
+                    .ok()
+                    .and_then(|algos| {
+                        let start = algos.find('[')?;
+                        let end = algos[start..].find(']')?;
+                        Some(algos[start + 1..start + end].to_string())
+                    });
+                Some(Self {
+                    name: entry.file_name().to_string_lossy().to_string(),
+                    disk_size,
+                    orig_data_size: *fields.first()?,
+                    compr_data_size: *fields.get(1)?,
+                    mem_used_total: *fields.get(2)?,
+                    algorithm,
+                })
+            })
+            .collect();
+        devices.sort_by(|a, b| a.name.cmp(&b.name));
+        devices
+    }
+
+    /// Uncompressed / compressed, None while empty
+    pub fn ratio(&self) -> Option<f64> {
+        (self.compr_data_size > 0).then(|| self.orig_data_size as f64 / self.compr_data_size as f64)
+    }
+}
+
+/// zswap settings from the module parameters
+pub struct ZswapInfo {
+    pub enabled: bool,
+    pub compressor: Option<String>,
+    /// Maximum share of RAM the pool may take
+    pub max_pool_percent: Option<u64>,
+}
+
+impl ZswapInfo {
+    fn read() -> Option<Self> {
+        let dir = Path::new("/sys/module/zswap/parameters");
+        let read = |file: &str| {
+            fs::read_to_string(dir.join(file))
+                .ok()
+                .map(|s| s.trim().to_string())
+        };
+        let enabled = read("enabled")?;
+        Some(Self {
+            enabled: enabled == "Y",
+            compressor: read("compressor"),
+            max_pool_percent: read("max_pool_percent").and_then(|p| p.parse().ok()),
+        })
+    }
+}
+
+/// An active swap area from /proc/swaps
+pub struct SwapDevice {
+    pub name: String,
+    /// "partition" or "file"
+    pub kind: String,
+    pub size: u64,
+    pub used: u64,
+    pub priority: i32,
+}
+
+impl SwapDevice {
+    fn scan() -> Vec<Self> {
+        let Ok(content) = fs::read_to_string("/proc/swaps") else {
+            return Vec::new();
+        };
+        // Filename Type Size Used Priority (sizes in KiB); paths escape spaces as \040
+        content
+            .lines()
+            .skip(1)
+            .filter_map(|line| {
+                let mut fields = line.split_whitespace();
+                Some(Self {
+                    name: fields.next()?.replace("\\040", " "),
+                    kind: fields.next()?.to_string(),
+                    size: fields.next()?.parse::<u64>().ok()? * 1024,
+                    used: fields.next()?.parse::<u64>().ok()? * 1024,
+                    priority: fields.next()?.parse().ok()?,
+                })
+            })
+            .collect()
+    }
+}
+
 pub struct MemoryInfo {
     pub total: u64,
     pub used: u64,
     pub swap_total: u64,
     pub swap_used: u64,
+    pub breakdown: MemoryBreakdown,
+    pub zram: Vec<ZramDevice>,
+    pub zswap: Option<ZswapInfo>,
+    pub swaps: Vec<SwapDevice>,
 }
 
 impl MemoryInfo {
     pub fn new(sys: &mut System) -> Self {
         sys.refresh_memory();
-        Self {
+        let mut info = Self {
             total: sys.total_memory(),
             used: sys.used_memory(),
             swap_total: sys.total_swap(),
             swap_used: sys.used_swap(),
+            breakdown: MemoryBreakdown::default(),
+            zram: Vec::new(),
+            zswap: ZswapInfo::read(),
+            swaps: Vec::new(),
+        };
+        info.refresh(sys);
+        info
+    }
+
+    /// Takes totals from an already refreshed `sys`, re-reads the rest from /proc and /sys
+    pub fn refresh(&mut self, sys: &System) {
+        self.total = sys.total_memory();
+        self.used = sys.used_memory();
+        self.swap_total = sys.total_swap();
+        self.swap_used = sys.used_swap();
+        self.breakdown = MemoryBreakdown::read();
+        self.zram = ZramDevice::scan();
+        self.swaps = SwapDevice::scan();
+    }
+
+    /// "Available 9.12 GiB | Buffers 312 MiB | Cached 4.10 GiB | Shared 512 MiB | Dirty 2.0 MiB"
+    pub fn get_formatted_breakdown(&self) -> String {
+        let b = &self.breakdown;
+        let mut out = format!(
+            "Available {} | Buffers {} | Cached {} | Shared {}",
+            format_size(b.available),
+            format_size(b.buffers),
+            format_size(b.cached),
+            format_size(b.shared)
+        );
+        if b.dirty + b.writeback > 0 {
+            out.push_str(&format!(
+                " | Dirty {} | Writeback {}",
+                format_size(b.dirty),
+                format_size(b.writeback)
+            ));
+        }
+        out
+    }
+
+    /// "0 / 512 x 2 MiB, THP 1.20 GiB"
+    pub fn get_formatted_huge_pages(&self) -> String {
+        let b = &self.breakdown;
+        let mut out = format!(
+            "{} / {} x {}",
+            b.huge_pages_total - b.huge_pages_free.min(b.huge_pages_total),
+            b.huge_pages_total,
+            format_size(b.huge_page_size)
+        );
+        if b.anon_huge > 0 {
+            out.push_str(&format!(", THP {}", format_size(b.anon_huge)));
+        }
+        out
+    }
+
+    /// "/dev/zram0 (zram, lzo-rle, 3.1:1) 512 MiB / 8.00 GiB, /swapfile 0 B / 2.00 GiB"
+    pub fn get_formatted_swaps(&self) -> String {
+        if self.swaps.is_empty() {
+            return "Disabled".to_string();
+        }
+        self.swaps
+            .iter()
+            .map(|swap| {
+                let zram = self
+                    .zram
+                    .iter()
+                    .find(|z| swap.name.ends_with(&format!("/{}", z.name)));
+                let detail = match zram {
+                    Some(z) => {
+                        let mut detail = "zram".to_string();
+                        if let Some(algorithm) = &z.algorithm {
+                            detail.push_str(&format!(", {}", algorithm));
+                        }
+                        if let Some(ratio) = z.ratio() {
+                            detail.push_str(&format!(", {:.1}:1", ratio));
+                        }
+                        detail
+                    }
+                    None => swap.kind.clone(),
+                };
+                format!(
+                    "{} ({}) {} / {}",
+                    swap.name,
+                    detail,
+                    format_size(swap.used),
+                    format_size(swap.size)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// "lz4, 120 MiB pool holding 410 MiB (3.4:1)" or "Disabled"
+    pub fn get_formatted_zswap(&self) -> String {
+        match &self.zswap {
+            Some(zswap) if zswap.enabled => {
+                let mut out = zswap
+                    .compressor
+                    .clone()
+                    .unwrap_or_else(|| "enabled".to_string());
+                if let Some((pool, stored)) = self.breakdown.zswap {
+                    out.push_str(&format!(
+                        ", {} pool holding {}",
+                        format_size(pool),
+                        format_size(stored)
+                    ));
+                    if pool > 0 {
+                        out.push_str(&format!(" ({:.1}:1)", stored as f64 / pool as f64));
+                    }
+                }
+                if let Some(percent) = zswap.max_pool_percent {
+                    out.push_str(&format!(", max {}%", percent));
+                }
+                out
+            }
+            _ => "Disabled".to_string(),
         }
     }
 }
+
+/// Bytes with a binary unit: "512 MiB", "4.10 GiB"
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if value >= 100.0 || unit < 2 {
+        format!("{:.0} {}", value, UNITS[unit])
+    } else {
+        format!("{:.2} {}", value, UNITS[unit])
+    }
+}
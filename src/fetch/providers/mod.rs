@@ -1,10 +1,26 @@
+pub mod ancestry;
 pub mod battery;
+pub mod block_devices;
+pub mod cgroup;
+pub mod connections;
 pub mod cpu;
+pub mod dir_size;
 pub mod disk;
+pub mod disk_io;
 pub mod display;
 pub mod gpu;
+pub mod host;
 pub mod memory;
 pub mod network;
 pub mod os;
+pub mod package_history;
 pub mod packages;
+pub mod pressure;
+pub mod routing;
+pub mod shell;
 pub mod style;
+pub mod terminal;
+pub mod terminal_style;
+pub mod version;
+pub mod virt;
+pub mod wm;
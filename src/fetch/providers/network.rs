@@ -1,51 +1,338 @@
+use crate::config::NetworkConfig;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs;
+use std::net::IpAddr;
+use std::path::Path;
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
 use sysinfo::Networks;
 
+/// Number of samples kept for the RX/TX charts
+pub const NET_HISTORY_LEN: usize = 60;
+
+/// How long an SSID lookup through `iw` stays valid
+const SSID_CACHE_TTL: Duration = Duration::from_secs(10);
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum InterfaceKind {
+    Ethernet,
+    Wireless,
+    Loopback,
+    Bridge,
+    Docker,
+    Veth,
+    Tun,
+    Wireguard,
+    Virtual,
+}
+
+impl InterfaceKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Ethernet => "ethernet",
+            Self::Wireless => "wifi",
+            Self::Loopback => "loopback",
+            Self::Bridge => "bridge",
+            Self::Docker => "docker",
+            Self::Veth => "veth",
+            Self::Tun => "tun/tap",
+            Self::Wireguard => "wireguard",
+            Self::Virtual => "virtual",
+        }
+    }
+
+    pub fn is_virtual(&self) -> bool {
+        !matches!(self, Self::Ethernet | Self::Wireless)
+    }
+}
+
+pub struct WirelessInfo {
+    pub ssid: Option<String>,
+    /// Link quality in percent (from the /70 scale in /proc/net/wireless)
+    pub quality: Option<u8>,
+    pub signal_dbm: Option<i32>,
+}
+
 pub struct NetworkInfo {
     pub name: String,
-    pub rx: u64,
-    pub tx: u64,
+    pub kind: InterfaceKind,
+    /// Bytes per second, computed from the real time between samples
+    pub rx_rate: f64,
+    pub tx_rate: f64,
     pub total_rx: u64,
     pub total_tx: u64,
-    pub ip_v4: String,
+    /// Every IPv4/IPv6 address with its prefix, e.g. "192.168.1.20/24"
+    pub addresses: Vec<String>,
+    pub mac: Option<String>,
+    pub mtu: Option<u32>,
+    /// "up", "down", "dormant", ... from /sys/class/net/<if>/operstate
+    pub operstate: String,
+    /// Link speed in Mbit/s, if the driver reports one
+    pub speed_mbps: Option<u32>,
+    pub wireless: Option<WirelessInfo>,
+    /// Oldest first, in bytes per second
+    pub rx_history: VecDeque<f64>,
+    pub tx_history: VecDeque<f64>,
 }
 
-pub struct NetworkProvider;
+impl NetworkInfo {
+    /// First IPv4 address, or the first global IPv6 address on IPv6-only links
+    pub fn primary_address(&self) -> &str {
+        self.addresses
+            .iter()
+            .find(|a| !a.contains(':'))
+            .or_else(|| self.addresses.iter().find(|a| !a.starts_with("fe80")))
+            .map_or("N/A", |a| a.as_str())
+    }
+}
+
+/// Keeps timestamped counters between refreshes so rates don't depend on
+/// the configured refresh rate (ticks drift, and the rate can change at runtime).
+#[derive(Default)]
+pub struct NetworkProvider {
+    previous: HashMap<String, (u64, u64)>,
+    last_sample: Option<Instant>,
+    history: HashMap<String, (VecDeque<f64>, VecDeque<f64>)>,
+    ssid_cache: HashMap<String, (Instant, Option<String>)>,
+    /// SSIDs read by background `iw` lookups, not yet moved into the cache
+    ssid_lookups: Arc<Mutex<HashMap<String, Option<String>>>>,
+    /// Interfaces with a lookup still running; a stuck `iw` isn't started again
+    ssid_pending: HashSet<String>,
+}
 
 impl NetworkProvider {
-    pub fn get_networks(net_handle: &Networks) -> Vec<NetworkInfo> {
-        net_handle
-            .iter()
-            .map(|(name, data)| {
-                let mut ip_v4 = "N/A".to_string();
-                for ip in data.ip_networks() {
-                    if let std::net::IpAddr::V4(ipv4) = ip.addr {
-                        ip_v4 = ipv4.to_string();
-                        break;
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get_networks(
+        &mut self,
+        net_handle: &Networks,
+        config: &NetworkConfig,
+    ) -> Vec<NetworkInfo> {
+        let now = Instant::now();
+        let elapsed = self
+            .last_sample
+            .map(|t| now.duration_since(t).as_secs_f64())
+            .unwrap_or(0.0);
+        let wireless_stats = Self::read_proc_wireless();
+
+        let mut current = HashMap::new();
+        let mut networks = Vec::new();
+
+        for (name, data) in net_handle {
+            let sys_path = Path::new("/sys/class/net").join(name);
+            let kind = Self::classify(name, &sys_path);
+            if config.hide_virtual && kind.is_virtual() {
+                continue;
+            }
+
+            let addresses = data
+                .ip_networks()
+                .iter()
+                .map(|ip| format!("{}/{}", ip.addr, ip.prefix))
+                .collect();
+
+            let total_rx = data.total_received();
+            let total_tx = data.total_transmitted();
+            current.insert(name.to_string(), (total_rx, total_tx));
+
+            // The first sample of an interface has no baseline, so it reports 0
+            let (rx_rate, tx_rate) = match self.previous.get(name) {
+                Some(&(prev_rx, prev_tx)) if elapsed > 0.0 => (
+                    total_rx.saturating_sub(prev_rx) as f64 / elapsed,
+                    total_tx.saturating_sub(prev_tx) as f64 / elapsed,
+                ),
+                _ => (0.0, 0.0),
+            };
+
+            let (rx_history, tx_history) = self.history.entry(name.to_string()).or_default();
+            if self.previous.contains_key(name) && elapsed > 0.0 {
+                for (history, rate) in [(&mut *rx_history, rx_rate), (&mut *tx_history, tx_rate)] {
+                    if history.len() == NET_HISTORY_LEN {
+                        history.pop_front();
                     }
+                    history.push_back(rate);
                 }
+            }
+            let (rx_history, tx_history) = (rx_history.clone(), tx_history.clone());
 
-                NetworkInfo {
-                    name: name.to_string(),
-                    rx: data.received(),
-                    tx: data.transmitted(),
-                    total_rx: data.total_received(),
-                    total_tx: data.total_transmitted(),
-                    ip_v4,
+            let wireless = (kind == InterfaceKind::Wireless).then(|| {
+                let (quality, signal_dbm) = wireless_stats.get(name).copied().unwrap_or_default();
+                WirelessInfo {
+                    ssid: self.cached_ssid(name, now),
+                    quality,
+                    signal_dbm,
                 }
+            });
+
+            let read = |file: &str| {
+                fs::read_to_string(sys_path.join(file))
+                    .ok()
+                    .map(|s| s.trim().to_string())
+            };
+            let mac = read("address").filter(|m| m != "00:00:00:00:00:00");
+            let mtu = read("mtu").and_then(|m| m.parse().ok());
+            let operstate = read("operstate").unwrap_or_else(|| "unknown".to_string());
+            // Reading "speed" fails (EINVAL) while the link is down and reports -1 for some drivers
+            let speed_mbps = read("speed")
+                .and_then(|s| s.parse::<i64>().ok())
+                .filter(|&s| s > 0)
+                .map(|s| s as u32);
+
+            networks.push(NetworkInfo {
+                name: name.to_string(),
+                kind,
+                rx_rate,
+                tx_rate,
+                total_rx,
+                total_tx,
+                addresses,
+                mac,
+                mtu,
+                operstate,
+                speed_mbps,
+                wireless,
+                rx_history,
+                tx_history,
+            });
+        }
+
+        // Drop state of interfaces that went away
+        self.history.retain(|name, _| current.contains_key(name));
+        self.ssid_cache.retain(|name, _| current.contains_key(name));
+        self.previous = current;
+        self.last_sample = Some(now);
+
+        // HashMap iteration order is random; keep rows stable
+        networks.sort_by(|a, b| a.name.cmp(&b.name));
+        networks
+    }
+
+    fn classify(name: &str, sys_path: &Path) -> InterfaceKind {
+        let devtype = fs::read_to_string(sys_path.join("uevent"))
+            .ok()
+            .and_then(|uevent| {
+                uevent
+                    .lines()
+                    .find_map(|l| l.strip_prefix("DEVTYPE=").map(|v| v.to_string()))
             })
-            .collect()
+            .unwrap_or_default();
+
+        if name == "lo" {
+            InterfaceKind::Loopback
+        } else if sys_path.join("wireless").exists() || devtype == "wlan" {
+            InterfaceKind::Wireless
+        } else if name.starts_with("docker") || name.starts_with("br-") {
+            InterfaceKind::Docker
+        } else if sys_path.join("bridge").exists() || devtype == "bridge" {
+            InterfaceKind::Bridge
+        } else if name.starts_with("veth") {
+            InterfaceKind::Veth
+        } else if sys_path.join("tun_flags").exists() {
+            InterfaceKind::Tun
+        } else if devtype == "wireguard" {
+            InterfaceKind::Wireguard
+        } else if !sys_path.join("device").exists() {
+            // Physical NICs have a backing device; everything else is software
+            InterfaceKind::Virtual
+        } else {
+            InterfaceKind::Ethernet
+        }
     }
 
-    pub fn get_local_ip(net_handle: &Networks) -> String {
-        for (name, network) in net_handle {
-            if name != "lo" {
-                for ip in network.ip_networks() {
-                    if let std::net::IpAddr::V4(ipv4) = ip.addr {
-                        return ipv4.to_string();
-                    }
+    /// Parses /proc/net/wireless:
+    /// Inter-| sta-|   Quality        |   Discarded packets
+    ///  face | tus | link level noise |  nwid  crypt   frag  retry   misc
+    ///  wlan0: 0000   54.  -56.  -256        0      0      0      0     12
+    fn read_proc_wireless() -> HashMap<String, (Option<u8>, Option<i32>)> {
+        let mut stats = HashMap::new();
+        let Ok(content) = fs::read_to_string("/proc/net/wireless") else {
+            return stats;
+        };
+
+        for line in content.lines().skip(2) {
+            let Some((name, rest)) = line.split_once(':') else {
+                continue;
+            };
+            let fields: Vec<&str> = rest.split_whitespace().collect();
+            let parse = |i: usize| {
+                fields
+                    .get(i)
+                    .and_then(|f| f.trim_end_matches('.').parse::<f64>().ok())
+            };
+            let quality = parse(1).map(|q| ((q / 70.0) * 100.0).clamp(0.0, 100.0) as u8);
+            let signal = parse(2).map(|s| s as i32);
+            stats.insert(name.trim().to_string(), (quality, signal));
+        }
+        stats
+    }
+
+    /// `iw` runs on a background thread so a slow or stuck one can't stall the
+    /// refresh; until it answers the previous SSID (or none) is shown
+    fn cached_ssid(&mut self, name: &str, now: Instant) -> Option<String> {
+        if let Ok(mut finished) = self.ssid_lookups.lock() {
+            for (iface, ssid) in finished.drain() {
+                self.ssid_pending.remove(&iface);
+                if let Some(entry) = self.ssid_cache.get_mut(&iface) {
+                    entry.1 = ssid;
                 }
             }
         }
-        "127.0.0.1".to_string()
+
+        let cached = self.ssid_cache.get(name);
+        let ssid = cached.and_then(|(_, ssid)| ssid.clone());
+        if cached.is_none_or(|(at, _)| now.duration_since(*at) >= SSID_CACHE_TTL)
+            && self.ssid_pending.insert(name.to_string())
+        {
+            self.ssid_cache
+                .insert(name.to_string(), (now, ssid.clone()));
+            let lookups = Arc::clone(&self.ssid_lookups);
+            let name = name.to_string();
+            thread::spawn(move || {
+                let ssid = Self::read_ssid(&name);
+                if let Ok(mut finished) = lookups.lock() {
+                    finished.insert(name, ssid);
+                }
+            });
+        }
+        ssid
+    }
+
+    /// `iw dev <if> link` prints "\tSSID: MyNetwork" while connected
+    fn read_ssid(name: &str) -> Option<String> {
+        let output = Command::new("iw")
+            .args(["dev", name, "link"])
+            .output()
+            .ok()?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        stdout
+            .lines()
+            .find_map(|l| l.trim().strip_prefix("SSID:"))
+            .map(|s| s.trim().to_string())
+    }
+
+    /// IPv4 of the interface carrying the default route. Without one, falls
+    /// back to the first non-loopback IPv4 (which may be a container bridge).
+    pub fn get_local_ip(net_handle: &Networks, uplink: Option<&str>) -> String {
+        let first_ipv4 = |name: &str| {
+            net_handle.get(name).and_then(|network| {
+                network.ip_networks().iter().find_map(|ip| match ip.addr {
+                    IpAddr::V4(ipv4) => Some(ipv4.to_string()),
+                    IpAddr::V6(_) => None,
+                })
+            })
+        };
+
+        if let Some(ip) = uplink.and_then(first_ipv4) {
+            return ip;
+        }
+        net_handle
+            .keys()
+            .filter(|name| name.as_str() != "lo")
+            .find_map(|name| first_ipv4(name))
+            .unwrap_or_else(|| "127.0.0.1".to_string())
     }
 }
@@ -1,101 +1,185 @@
+use super::shell::ShellInfo;
+use super::terminal::{MultiplexerInfo, TerminalInfo};
+use super::virt::VirtInfo;
+use super::wm::{SessionType, WmInfo};
+use crate::config::ShellConfig;
+use std::collections::HashMap;
+use std::fs;
 use sysinfo::System;
 
+/// Fields of os-release(5) used for naming the distro and picking its logo
+#[derive(Default)]
+pub struct OsRelease {
+    pub name: Option<String>,
+    pub pretty_name: Option<String>,
+    /// Lowercase identifier, e.g. "endeavouros"
+    pub id: Option<String>,
+    /// Parent distros, closest first, e.g. ["ubuntu", "debian"] on Pop!_OS
+    pub id_like: Vec<String>,
+    pub version_id: Option<String>,
+    pub build_id: Option<String>,
+    pub variant: Option<String>,
+}
+
+impl OsRelease {
+    /// Reads /etc/os-release, falling back to /usr/lib/os-release as the spec requires
+    pub fn new() -> Self {
+        ["/etc/os-release", "/usr/lib/os-release"]
+            .iter()
+            .find_map(|path| fs::read_to_string(path).ok())
+            .map(|content| Self::parse(&content))
+            .unwrap_or_default()
+    }
+
+    fn parse(content: &str) -> Self {
+        let mut values: HashMap<&str, String> = content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| line.split_once('='))
+            .map(|(key, value)| (key.trim(), Self::unquote(value.trim())))
+            .filter(|(_, value)| !value.is_empty())
+            .collect();
+
+        Self {
+            name: values.remove("NAME"),
+            pretty_name: values.remove("PRETTY_NAME"),
+            id: values.remove("ID").map(|id| id.to_lowercase()),
+            id_like: values
+                .remove("ID_LIKE")
+                .map(|like| {
+                    like.split_whitespace()
+                        .map(|id| id.to_lowercase())
+                        .collect()
+                })
+                .unwrap_or_default(),
+            version_id: values.remove("VERSION_ID"),
+            build_id: values.remove("BUILD_ID"),
+            variant: values.remove("VARIANT"),
+        }
+    }
+
+    /// Values are shell-style: optionally quoted, with backslash escapes in double quotes
+    fn unquote(value: &str) -> String {
+        if let Some(inner) = value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+            let mut out = String::with_capacity(inner.len());
+            let mut chars = inner.chars();
+            while let Some(c) = chars.next() {
+                if c == '\\' {
+                    out.extend(chars.next());
+                } else {
+                    out.push(c);
+                }
+            }
+            out
+        } else if let Some(inner) = value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')) {
+            inner.to_string()
+        } else {
+            value.to_string()
+        }
+    }
+
+    /// "Fedora Linux 40 (Workstation Edition)", built from NAME/VERSION_ID when
+    /// PRETTY_NAME is missing
+    pub fn display_name(&self) -> Option<String> {
+        let mut name = self.pretty_name.clone().or_else(|| {
+            let name = self.name.clone()?;
+            Some(match self.version_id.as_ref().or(self.build_id.as_ref()) {
+                Some(version) => format!("{} {}", name, version),
+                None => name,
+            })
+        })?;
+        if let Some(variant) = &self.variant
+            && !name.contains(variant.as_str())
+        {
+            name.push_str(&format!(" ({})", variant));
+        }
+        Some(name)
+    }
+
+    /// IDs to try when picking a logo: ID first, then ID_LIKE in order
+    pub fn logo_ids(&self) -> Vec<&str> {
+        self.id
+            .iter()
+            .chain(self.id_like.iter())
+            .map(|id| id.as_str())
+            .collect()
+    }
+}
+
 pub struct OsInfo {
     pub name: String,
+    pub release: OsRelease,
     pub kernel: String,
     pub hostname: String,
     pub shell: String,
     pub de_wm: String,
     pub wm: String,
     pub terminal: String,
+    pub multiplexer: String,
     pub locale: String,
 }
 
 impl OsInfo {
-    pub fn new(sys: &mut System) -> Self {
-        let name = System::name().unwrap_or_else(|| "Unknown".to_string());
-        let kernel = System::kernel_version().unwrap_or_else(|| "Unknown".to_string());
+    pub fn new(virt: &VirtInfo, shell_config: &ShellConfig) -> Self {
+        let release = OsRelease::new();
+        let name = release
+            .display_name()
+            .or_else(System::name)
+            .unwrap_or_else(|| "Unknown".to_string());
+        let mut kernel = System::kernel_version().unwrap_or_else(|| "Unknown".to_string());
+        if virt.in_container() {
+            // Containers share the host's kernel
+            kernel.push_str(" (host)");
+        }
         let hostname = System::host_name().unwrap_or_else(|| "localhost".to_string());
 
-        // Shell
-        let shell_path = std::env::var("SHELL").unwrap_or_else(|_| "Unknown".to_string());
-        let shell = shell_path
-            .split('/')
-            .next_back()
-            .unwrap_or("Unknown")
-            .to_string();
+        let shell = ShellInfo::new(shell_config).summary();
 
         // DE/WM
         let de_wm = std::env::var("XDG_CURRENT_DESKTOP")
             .or_else(|_| std::env::var("DESKTOP_SESSION"))
             .unwrap_or_else(|_| "Unknown".to_string());
 
-        let wm = Self::detect_wm(sys);
-        let terminal = Self::detect_terminal(sys);
+        let session = SessionType::detect();
+        let mut wm = match WmInfo::detect(session) {
+            Some(wm) => wm.summary(),
+            None if session == SessionType::Tty => "None (TTY)".to_string(),
+            None => "Unknown".to_string(),
+        };
+        let multiplexer = MultiplexerInfo::detect();
+        let mut terminal = TerminalInfo::detect(multiplexer.as_ref())
+            .map(|t| t.summary())
+            .unwrap_or_else(|| "Unknown".to_string());
+        let multiplexer = multiplexer
+            .map(|m| m.summary())
+            .unwrap_or_else(|| "None".to_string());
+
+        // A container only sees its own PID namespace: the compositor and the
+        // terminal emulator run on the host, so fall back to what the session exports
+        if virt.in_container() {
+            if wm == "Unknown" {
+                wm = std::env::var("XDG_SESSION_DESKTOP")
+                    .map(|wm| format!("{} (host)", wm))
+                    .unwrap_or_else(|_| "N/A (container)".to_string());
+            }
+            if terminal == "Unknown" {
+                terminal = "N/A (container)".to_string();
+            }
+        }
         let locale = std::env::var("LANG").unwrap_or_else(|_| "Unknown".to_string());
 
         Self {
             name,
+            release,
             kernel,
             hostname,
             shell,
             de_wm,
             wm,
             terminal,
+            multiplexer,
             locale,
         }
     }
-
-    fn detect_wm(sys: &System) -> String {
-        let wms = [
-            "kwin_wayland",
-            "kwin_x11",
-            "gnome-shell",
-            "mutter",
-            "sway",
-            "hyprland",
-            "openbox",
-            "i3",
-            "bspwm",
-            "xfwm4",
-            "metacity",
-            "weston",
-            "labwc",
-            "wayfire",
-        ];
-
-        for (_pid, process) in sys.processes() {
-            let name = process.name().to_string_lossy();
-            for wm in wms {
-                if name.contains(wm) {
-                    if wm.starts_with("kwin") {
-                        return "KWin".to_string();
-                    }
-                    if wm == "gnome-shell" {
-                        return "Mutter (GNOME)".to_string();
-                    }
-                    return wm.to_string();
-                }
-            }
-        }
-        "Unknown".to_string()
-    }
-
-    fn detect_terminal(sys: &mut System) -> String {
-        // refresh_processes is heavy, so it should be called carefully.
-        // In the original code it was called inside detect_terminal.
-        // We will assume the caller might have refreshed it or we do it here strictly.
-        // For safety, let's refresh here as it relies on PIDs which might be stale if not refreshed.
-        sys.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
-        if let Ok(pid) = sysinfo::get_current_pid()
-            && let Some(process) = sys.process(pid)
-            && let Some(parent_pid) = process.parent()
-            && let Some(parent) = sys.process(parent_pid)
-            && let Some(grandparent_pid) = parent.parent()
-            && let Some(grandparent) = sys.process(grandparent_pid)
-        {
-            return grandparent.name().to_string_lossy().to_string();
-        }
-        "Unknown".to_string()
-    }
 }
@@ -0,0 +1,319 @@
+use std::fs;
+use std::path::Path;
+
+use super::packages::PackageManager;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum TransactionAction {
+    Installed,
+    Upgraded,
+    Downgraded,
+    Reinstalled,
+    Removed,
+}
+
+impl TransactionAction {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Installed => "installed",
+            Self::Upgraded => "upgraded",
+            Self::Downgraded => "downgraded",
+            Self::Reinstalled => "reinstalled",
+            Self::Removed => "removed",
+        }
+    }
+}
+
+pub struct PackageTransaction {
+    /// "YYYY-MM-DD HH:MM" in the log's local time
+    pub timestamp: String,
+    pub action: TransactionAction,
+    pub package: String,
+    /// "old -> new" for upgrades, the single version otherwise
+    pub version: String,
+}
+
+pub struct PackageHistory {
+    pub manager: PackageManager,
+    pub log_path: String,
+    /// Newest first
+    pub transactions: Vec<PackageTransaction>,
+    pub last_full_upgrade: Option<String>,
+}
+
+impl PackageHistory {
+    /// Parses the package manager's log, keeping the last `limit` transactions.
+    /// Returns None when no supported log is readable.
+    pub fn new(limit: usize) -> Option<Self> {
+        let pacman_log = Path::new("/var/log/pacman.log");
+        let dpkg_log = Path::new("/var/log/dpkg.log");
+        let apt_log = Path::new("/var/log/apt/history.log");
+
+        let mut history = if let Ok(content) = fs::read_to_string(pacman_log) {
+            Self::parse_pacman(&content, pacman_log)
+        } else if let Ok(content) = fs::read_to_string(dpkg_log) {
+            let mut history = Self::parse_dpkg(&content, dpkg_log);
+            // dpkg doesn't know about upgrades as a whole, apt does
+            if let Ok(apt) = fs::read_to_string(apt_log) {
+                history.last_full_upgrade = Self::parse_apt_last_upgrade(&apt);
+            }
+            history
+        } else if let Ok(content) = fs::read_to_string(apt_log) {
+            Self::parse_apt(&content, apt_log)
+        } else {
+            return None;
+        };
+
+        history.transactions.reverse();
+        history.transactions.truncate(limit);
+        Some(history)
+    }
+
+    /// Transactions that share the newest transaction's date.
+    pub fn latest_day(&self) -> impl Iterator<Item = &PackageTransaction> {
+        let day = self
+            .transactions
+            .first()
+            .map(|t| t.timestamp.get(..10).unwrap_or_default())
+            .unwrap_or_default();
+        self.transactions
+            .iter()
+            .take_while(move |t| t.timestamp.starts_with(day))
+    }
+
+    /// pacman.log timestamps are either "[2019-01-01 10:22]" or "[2024-05-01T10:22:33+0200]"
+    fn pacman_timestamp(raw: &str) -> String {
+        let raw = raw.replace('T', " ");
+        raw.get(..16).unwrap_or(&raw).to_string()
+    }
+
+    /// Example lines:
+    /// [2024-05-01T10:22:33+0200] [PACMAN] starting full system upgrade
+    /// [2024-05-01T10:22:40+0200] [ALPM] upgraded linux (6.8.1.arch1-1 -> 6.8.2.arch1-1)
+    fn parse_pacman(content: &str, path: &Path) -> Self {
+        let mut transactions = Vec::new();
+        let mut last_full_upgrade = None;
+
+        for line in content.lines() {
+            let Some(rest) = line.strip_prefix('[') else {
+                continue;
+            };
+            let Some((timestamp, rest)) = rest.split_once("] ") else {
+                continue;
+            };
+
+            if rest.ends_with("starting full system upgrade") {
+                last_full_upgrade = Some(Self::pacman_timestamp(timestamp));
+                continue;
+            }
+
+            let Some(rest) = rest.strip_prefix("[ALPM] ") else {
+                continue;
+            };
+            let Some((verb, rest)) = rest.split_once(' ') else {
+                continue;
+            };
+            let action = match verb {
+                "installed" => TransactionAction::Installed,
+                "upgraded" => TransactionAction::Upgraded,
+                "downgraded" => TransactionAction::Downgraded,
+                "reinstalled" => TransactionAction::Reinstalled,
+                "removed" => TransactionAction::Removed,
+                _ => continue,
+            };
+            let Some((package, version)) = rest.split_once(" (") else {
+                continue;
+            };
+
+            transactions.push(PackageTransaction {
+                timestamp: Self::pacman_timestamp(timestamp),
+                action,
+                package: package.to_string(),
+                version: version.trim_end_matches(')').to_string(),
+            });
+        }
+
+        Self {
+            manager: PackageManager::Pacman,
+            log_path: path.display().to_string(),
+            transactions,
+            last_full_upgrade,
+        }
+    }
+
+    /// Example lines:
+    /// 2024-05-01 10:22:33 install htop:amd64 <none> 3.3.0-4
+    /// 2024-05-01 10:22:35 upgrade libc6:amd64 2.36-9 2.36-9+deb12u7
+    /// 2024-05-01 10:22:37 remove nano:amd64 7.2-1 <none>
+    fn parse_dpkg(content: &str, path: &Path) -> Self {
+        let mut transactions = Vec::new();
+
+        for line in content.lines() {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            if parts.len() < 6 {
+                continue;
+            }
+            let (old, new) = (parts[4], parts[5]);
+            let action = match parts[2] {
+                "install" if old == "<none>" => TransactionAction::Installed,
+                "install" if old == new => TransactionAction::Reinstalled,
+                "install" | "upgrade" => TransactionAction::Upgraded,
+                "remove" | "purge" => TransactionAction::Removed,
+                _ => continue,
+            };
+            // Keep the architecture suffix out, it is almost always the native one
+            let package = parts[3].split(':').next().unwrap_or(parts[3]);
+            // A purge follows the remove of the same package (after any other removes
+            // in the same run); only keep one of them
+            if parts[2] == "purge"
+                && transactions
+                    .iter()
+                    .rev()
+                    .take_while(|t: &&PackageTransaction| t.action == TransactionAction::Removed)
+                    .any(|t| t.package == package)
+            {
+                continue;
+            }
+
+            let version = match action {
+                TransactionAction::Installed | TransactionAction::Reinstalled => new.to_string(),
+                TransactionAction::Removed => old.to_string(),
+                _ => format!("{} -> {}", old, new),
+            };
+
+            transactions.push(PackageTransaction {
+                timestamp: format!("{} {}", parts[0], parts[1].get(..5).unwrap_or(parts[1])),
+                action,
+                package: package.to_string(),
+                version,
+            });
+        }
+
+        Self {
+            manager: PackageManager::Dpkg,
+            log_path: path.display().to_string(),
+            transactions,
+            last_full_upgrade: None,
+        }
+    }
+
+    /// "Start-Date: 2024-05-01  10:22:33" -> "2024-05-01 10:22"
+    fn apt_timestamp(raw: &str) -> String {
+        let mut parts = raw.split_whitespace();
+        let date = parts.next().unwrap_or_default();
+        let time = parts.next().unwrap_or_default();
+        format!("{} {}", date, time.get(..5).unwrap_or(time))
+    }
+
+    fn is_apt_full_upgrade(commandline: &str) -> bool {
+        commandline
+            .split_whitespace()
+            .any(|arg| matches!(arg, "upgrade" | "full-upgrade" | "dist-upgrade"))
+    }
+
+    fn parse_apt_last_upgrade(content: &str) -> Option<String> {
+        let mut start = None;
+        let mut last = None;
+        for line in content.lines() {
+            if let Some(date) = line.strip_prefix("Start-Date:") {
+                start = Some(Self::apt_timestamp(date));
+            } else if let Some(cmd) = line.strip_prefix("Commandline:")
+                && Self::is_apt_full_upgrade(cmd)
+            {
+                last = start.clone();
+            }
+        }
+        last
+    }
+
+    /// Example stanza:
+    /// Start-Date: 2024-05-01  10:22:33
+    /// Commandline: apt full-upgrade
+    /// Upgrade: libc6:amd64 (2.36-9, 2.36-9+deb12u7), tzdata:amd64 (2024a-0, 2024b-0)
+    /// End-Date: 2024-05-01  10:22:50
+    fn parse_apt(content: &str, path: &Path) -> Self {
+        let mut transactions = Vec::new();
+        let mut start = String::new();
+
+        for line in content.lines() {
+            if let Some(date) = line.strip_prefix("Start-Date:") {
+                start = Self::apt_timestamp(date);
+                continue;
+            }
+            let Some((key, list)) = line.split_once(": ") else {
+                continue;
+            };
+            let action = match key {
+                "Install" => TransactionAction::Installed,
+                "Upgrade" => TransactionAction::Upgraded,
+                "Downgrade" => TransactionAction::Downgraded,
+                "Reinstall" => TransactionAction::Reinstalled,
+                "Remove" | "Purge" => TransactionAction::Removed,
+                _ => continue,
+            };
+
+            // "name:arch (v1, v2), name2:arch (v1)" -> split on "), "
+            for entry in list.split("), ") {
+                let Some((package, versions)) = entry.split_once(" (") else {
+                    continue;
+                };
+                let versions: Vec<&str> = versions
+                    .trim_end_matches(')')
+                    .split(", ")
+                    .filter(|v| *v != "automatic")
+                    .collect();
+                let version = match (action, versions.as_slice()) {
+                    (TransactionAction::Upgraded | TransactionAction::Downgraded, [old, new]) => {
+                        format!("{} -> {}", old, new)
+                    }
+                    (_, [v, ..]) => v.to_string(),
+                    _ => String::new(),
+                };
+
+                transactions.push(PackageTransaction {
+                    timestamp: start.clone(),
+                    action,
+                    package: package.split(':').next().unwrap_or(package).to_string(),
+                    version,
+                });
+            }
+        }
+
+        Self {
+            manager: PackageManager::Dpkg,
+            log_path: path.display().to_string(),
+            last_full_upgrade: Self::parse_apt_last_upgrade(content),
+            transactions,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dpkg_purge_after_remove_is_listed_once() {
+        let log = "\
+2024-05-01 10:22:33 install htop:amd64 <none> 3.3.0-4
+2024-05-01 10:22:37 remove nano:amd64 7.2-1 <none>
+2024-05-01 10:22:37 status config-files nano:amd64 7.2-1
+2024-05-01 10:22:38 purge nano:amd64 7.2-1 <none>
+2024-05-01 10:22:38 status not-installed nano:amd64 <none>
+";
+        let history = PackageHistory::parse_dpkg(log, Path::new("/var/log/dpkg.log"));
+        let packages: Vec<(&str, TransactionAction)> = history
+            .transactions
+            .iter()
+            .map(|t| (t.package.as_str(), t.action))
+            .collect();
+        assert!(
+            packages
+                == [
+                    ("htop", TransactionAction::Installed),
+                    ("nano", TransactionAction::Removed)
+                ]
+        );
+        assert_eq!(history.transactions[1].version, "7.2-1");
+    }
+}
@@ -1,68 +1,229 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::mpsc;
+use std::thread;
 
-pub struct PackageProvider;
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PackageManager {
+    Pacman,
+    Dpkg,
+    Rpm,
+    Apk,
+    Xbps,
+    Portage,
+    NixSystem,
+    NixDefault,
+    NixUser,
+    FlatpakSystem,
+    FlatpakUser,
+    Snap,
+}
 
-impl PackageProvider {
-    // This function is potentially slow and fits well for async execution
-    pub fn count_packages() -> String {
-        let mut pkgs = Vec::new();
-        // Pacman
-        if let Ok(output) = Command::new("sh")
-            .arg("-c")
-            .arg("pacman -Qq | wc -l")
-            .output()
-        {
-            let count = String::from_utf8(output.stdout)
-                .unwrap_or_default()
-                .trim()
-                .to_string();
-            if !count.is_empty() && count != "0" {
-                pkgs.push(format!("{} (pacman)", count));
-            }
+impl PackageManager {
+    pub const ALL: [PackageManager; 12] = [
+        Self::Pacman,
+        Self::Dpkg,
+        Self::Rpm,
+        Self::Apk,
+        Self::Xbps,
+        Self::Portage,
+        Self::NixSystem,
+        Self::NixDefault,
+        Self::NixUser,
+        Self::FlatpakSystem,
+        Self::FlatpakUser,
+        Self::Snap,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Pacman => "pacman",
+            Self::Dpkg => "dpkg",
+            Self::Rpm => "rpm",
+            Self::Apk => "apk",
+            Self::Xbps => "xbps",
+            Self::Portage => "emerge",
+            Self::NixSystem => "nix-system",
+            Self::NixDefault => "nix-default",
+            Self::NixUser => "nix-user",
+            Self::FlatpakSystem => "flatpak-system",
+            Self::FlatpakUser => "flatpak-user",
+            Self::Snap => "snap",
         }
-        // Dpkg (Debian/Ubuntu)
-        if let Ok(output) = Command::new("sh")
-            .arg("-c")
-            .arg("dpkg-query -f '${binary:Package}\n' -W | wc -l")
-            .output()
-        {
-            let count = String::from_utf8(output.stdout)
-                .unwrap_or_default()
-                .trim()
-                .to_string();
-            if !count.is_empty() && count != "0" {
-                pkgs.push(format!("{} (dpkg)", count));
+    }
+
+    /// Counts installed packages, returning None if the manager isn't present
+    /// (or has nothing installed).
+    pub fn count(&self) -> Option<usize> {
+        let home = dirs::home_dir().unwrap_or_default();
+        let count = match self {
+            Self::Pacman => PackageProvider::count_pacman(Path::new("/var/lib/pacman/local")),
+            Self::Dpkg => PackageProvider::count_dpkg(Path::new("/var/lib/dpkg/status")),
+            Self::Rpm => PackageProvider::count_rpm(),
+            Self::Apk => PackageProvider::count_apk(Path::new("/lib/apk/db/installed")),
+            Self::Xbps => PackageProvider::count_xbps(Path::new("/var/db/xbps")),
+            Self::Portage => PackageProvider::count_portage(Path::new("/var/db/pkg")),
+            Self::NixSystem => PackageProvider::count_nix(Path::new("/run/current-system/sw")),
+            Self::NixDefault => {
+                PackageProvider::count_nix(Path::new("/nix/var/nix/profiles/default"))
             }
+            Self::NixUser => PackageProvider::count_nix(&home.join(".nix-profile"))
+                .or_else(|| PackageProvider::count_nix(&home.join(".local/state/nix/profile"))),
+            Self::FlatpakSystem => PackageProvider::count_flatpak(Path::new("/var/lib/flatpak")),
+            Self::FlatpakUser => PackageProvider::count_flatpak(&home.join(".local/share/flatpak")),
+            Self::Snap => PackageProvider::count_snap(Path::new("/snap")),
+        };
+        count.filter(|&c| c > 0)
+    }
+}
+
+pub struct PackageCount {
+    pub manager: PackageManager,
+    pub count: usize,
+}
+
+pub struct PackageProvider;
+
+impl PackageProvider {
+    /// Counts every package manager on its own thread. Each result is sent as
+    /// soon as it is ready; the channel disconnects once all of them finished.
+    pub fn spawn_counters() -> mpsc::Receiver<PackageCount> {
+        let (tx, rx) = mpsc::channel();
+        for manager in PackageManager::ALL {
+            let tx = tx.clone();
+            thread::spawn(move || {
+                if let Some(count) = manager.count() {
+                    let _ = tx.send(PackageCount { manager, count });
+                }
+            });
         }
-        // RPM (Fedora/RHEL)
-        if let Ok(output) = Command::new("sh").arg("-c").arg("rpm -qa | wc -l").output() {
-            let count = String::from_utf8(output.stdout)
-                .unwrap_or_default()
-                .trim()
-                .to_string();
-            if !count.is_empty() && count != "0" {
-                pkgs.push(format!("{} (rpm)", count));
-            }
+        rx
+    }
+
+    /// "1234 (pacman), 12 (flatpak-system)"
+    pub fn format_counts(counts: &[PackageCount]) -> String {
+        counts
+            .iter()
+            .map(|c| format!("{} ({})", c.count, c.manager.label()))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn count_dirs(path: &Path) -> Option<usize> {
+        let entries = fs::read_dir(path).ok()?;
+        Some(entries.flatten().filter(|e| e.path().is_dir()).count())
+    }
+
+    /// One directory per installed package ("name-version-rel")
+    fn count_pacman(path: &Path) -> Option<usize> {
+        Self::count_dirs(path)
+    }
+
+    /// Counts stanzas that are actually installed (removed packages keep their "config-files" entry)
+    fn count_dpkg(path: &Path) -> Option<usize> {
+        let content = fs::read_to_string(path).ok()?;
+        Some(
+            content
+                .lines()
+                .filter(|l| l.starts_with("Status: ") && l.ends_with(" installed"))
+                .filter(|l| l.contains(" ok "))
+                .count(),
+        )
+    }
+
+    /// The rpm database is SQLite (or BerkeleyDB on older systems), so there is
+    /// no cheap native format to parse. Run rpm directly instead of through a shell pipeline.
+    fn count_rpm() -> Option<usize> {
+        let output = Command::new("rpm").arg("-qa").output().ok()?;
+        if !output.status.success() {
+            return None;
         }
-        // Flatpak
-        if let Ok(output) = Command::new("sh")
-            .arg("-c")
-            .arg("flatpak list --app | wc -l")
-            .output()
+        Some(
+            output
+                .stdout
+                .split(|&b| b == b'\n')
+                .filter(|l| !l.is_empty())
+                .count(),
+        )
+    }
+
+    /// Each package record carries a single "P:" (package name) line
+    fn count_apk(path: &Path) -> Option<usize> {
+        let content = fs::read_to_string(path).ok()?;
+        Some(content.lines().filter(|l| l.starts_with("P:")).count())
+    }
+
+    /// pkgdb-<version>.plist stores a "state" key per package
+    fn count_xbps(dir: &Path) -> Option<usize> {
+        let pkgdb = fs::read_dir(dir)
+            .ok()?
+            .flatten()
+            .map(|e| e.path())
+            .find(|p| {
+                let name = p.file_name().unwrap_or_default().to_string_lossy();
+                name.starts_with("pkgdb-") && name.ends_with(".plist")
+            })?;
+        let content = fs::read_to_string(pkgdb).ok()?;
+        Some(content.matches("<string>installed</string>").count())
+    }
+
+    /// /var/db/pkg/<category>/<package-version>
+    fn count_portage(path: &Path) -> Option<usize> {
+        let categories = fs::read_dir(path).ok()?;
+        Some(
+            categories
+                .flatten()
+                .filter_map(|c| Self::count_dirs(&c.path()))
+                .sum(),
+        )
+    }
+
+    /// Counts the distinct store paths a profile links to. New-style profiles
+    /// (`nix profile`) list their packages in manifest.json instead.
+    fn count_nix(profile: &Path) -> Option<usize> {
+        if let Ok(manifest) = fs::read_to_string(profile.join("manifest.json"))
+            && let Some(elements) = manifest.split("\"elements\"").nth(1)
         {
-            let count = String::from_utf8(output.stdout)
-                .unwrap_or_default()
-                .trim()
-                .to_string();
-            if !count.is_empty() && count != "0" {
-                pkgs.push(format!("{} (flatpak-user)", count));
-            }
+            return Some(elements.matches("\"storePaths\"").count());
         }
 
-        if pkgs.is_empty() {
-            "Unknown".to_string()
-        } else {
-            pkgs.join(", ")
+        let entries = fs::read_dir(profile.join("bin")).ok()?;
+        let store_paths: HashSet<PathBuf> = entries
+            .flatten()
+            .filter_map(|e| fs::read_link(e.path()).ok())
+            .filter(|target| target.starts_with("/nix/store"))
+            .map(|target| target.components().take(4).collect())
+            .collect();
+        Some(store_paths.len())
+    }
+
+    /// Apps are counted per id, runtimes per id/arch/branch, matching `flatpak list`
+    fn count_flatpak(installation: &Path) -> Option<usize> {
+        let apps = Self::count_dirs(&installation.join("app"));
+        let runtimes = fs::read_dir(installation.join("runtime")).ok().map(|ids| {
+            ids.flatten()
+                .filter_map(|id| fs::read_dir(id.path()).ok())
+                .flat_map(|arches| arches.flatten())
+                .filter_map(|arch| Self::count_dirs(&arch.path()))
+                .sum::<usize>()
+        });
+
+        match (apps, runtimes) {
+            (None, None) => None,
+            (a, r) => Some(a.unwrap_or(0) + r.unwrap_or(0)),
         }
     }
+
+    /// Every mounted snap has a directory under /snap; "bin" holds the wrappers
+    fn count_snap(path: &Path) -> Option<usize> {
+        let entries = fs::read_dir(path).ok()?;
+        Some(
+            entries
+                .flatten()
+                .filter(|e| e.path().is_dir() && e.file_name() != "bin")
+                .count(),
+        )
+    }
 }
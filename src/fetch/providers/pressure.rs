@@ -0,0 +1,174 @@
+use std::fs;
+
+/// How loaded a resource is, for color-coding
+#[derive(Clone, Copy, PartialEq, PartialOrd)]
+pub enum Level {
+    Ok,
+    Warn,
+    Critical,
+}
+
+/// One line of a /proc/pressure file: share of wall time tasks were stalled
+#[derive(Clone, Copy, Default)]
+pub struct PsiAverages {
+    pub avg10: f32,
+    pub avg60: f32,
+    pub avg300: f32,
+}
+
+/// `some`: at least one task stalled; `full`: all non-idle tasks stalled at once
+/// (not reported for CPU before 5.13)
+#[derive(Clone, Copy, Default)]
+pub struct Psi {
+    pub some: PsiAverages,
+    pub full: Option<PsiAverages>,
+}
+
+impl Psi {
+    /// "some avg10=0.12 avg60=0.05 avg300=0.01 total=12345"
+    fn read(resource: &str) -> Option<Self> {
+        let content = fs::read_to_string(format!("/proc/pressure/{}", resource)).ok()?;
+        let mut psi = Self::default();
+        for line in content.lines() {
+            let mut fields = line.split_whitespace();
+            let kind = fields.next()?;
+            let mut averages = PsiAverages::default();
+            for field in fields {
+                let Some((key, value)) = field.split_once('=') else {
+                    continue;
+                };
+                let value = value.parse().unwrap_or(0.0);
+                match key {
+                    "avg10" => averages.avg10 = value,
+                    "avg60" => averages.avg60 = value,
+                    "avg300" => averages.avg300 = value,
+                    _ => {}
+                }
+            }
+            match kind {
+                "some" => psi.some = averages,
+                "full" => psi.full = Some(averages),
+                _ => {}
+            }
+        }
+        Some(psi)
+    }
+
+    /// Any full stall is worse than a lot of partial ones
+    pub fn level(&self) -> Level {
+        let full = self.full.map_or(0.0, |f| f.avg10);
+        if full >= 5.0 || self.some.avg10 >= 25.0 {
+            Level::Critical
+        } else if full >= 1.0 || self.some.avg10 >= 5.0 {
+            Level::Warn
+        } else {
+            Level::Ok
+        }
+    }
+}
+
+/// Load average, run queue and pressure stall information (PSI, 4.20+)
+#[derive(Default)]
+pub struct PressureInfo {
+    /// 1, 5 and 15 minute load averages
+    pub load: [f64; 3],
+    /// Tasks currently runnable and blocked on I/O, from /proc/stat
+    pub procs_running: u32,
+    pub procs_blocked: u32,
+    /// Threads that exist, from /proc/loadavg
+    pub threads: u32,
+    /// None when the kernel lacks PSI or it's disabled (psi=0)
+    pub cpu: Option<Psi>,
+    pub memory: Option<Psi>,
+    pub io: Option<Psi>,
+}
+
+impl PressureInfo {
+    pub fn new() -> Self {
+        let mut info = Self::default();
+        info.refresh();
+        info
+    }
+
+    pub fn refresh(&mut self) {
+        // "0.52 0.58 0.59 2/1234 5678"
+        if let Ok(loadavg) = fs::read_to_string("/proc/loadavg") {
+            let fields: Vec<&str> = loadavg.split_whitespace().collect();
+            for (i, slot) in self.load.iter_mut().enumerate() {
+                *slot = fields.get(i).and_then(|f| f.parse().ok()).unwrap_or(0.0);
+            }
+            self.threads = fields
+                .get(3)
+                .and_then(|f| f.split_once('/'))
+                .and_then(|(_, total)| total.parse().ok())
+                .unwrap_or(0);
+        }
+
+        if let Ok(stat) = fs::read_to_string("/proc/stat") {
+            let value = |key: &str| {
+                stat.lines()
+                    .find_map(|line| line.strip_prefix(key)?.trim().parse().ok())
+                    .unwrap_or(0)
+            };
+            self.procs_running = value("procs_running");
+            self.procs_blocked = value("procs_blocked");
+        }
+
+        self.cpu = Psi::read("cpu");
+        self.memory = Psi::read("memory");
+        self.io = Psi::read("io");
+    }
+
+    /// The 1-minute load against the number of logical CPUs
+    pub fn load_level(&self, cores: usize) -> Level {
+        let per_core = self.load[0] / cores.max(1) as f64;
+        if per_core >= 1.0 {
+            Level::Critical
+        } else if per_core >= 0.7 {
+            Level::Warn
+        } else {
+            Level::Ok
+        }
+    }
+
+    /// "0.52, 0.58, 0.59 (13% of 4 CPUs), 2 running, 0 blocked, 1234 threads"
+    pub fn get_formatted_load(&self, cores: usize) -> String {
+        format!(
+            "{:.2}, {:.2}, {:.2} ({:.0}% of {} CPUs), {} running, {} blocked, {} threads",
+            self.load[0],
+            self.load[1],
+            self.load[2],
+            self.load[0] / cores.max(1) as f64 * 100.0,
+            cores,
+            self.procs_running,
+            self.procs_blocked,
+            self.threads
+        )
+    }
+
+    /// "CPU 1.2/0.8/0.5% | I/O 3.4/2.0/1.1% (full 1.1/0.6/0.2%)", avg10/avg60/avg300
+    pub fn get_formatted_pressure(&self) -> String {
+        let averages = |a: &PsiAverages| format!("{:.1}/{:.1}/{:.1}%", a.avg10, a.avg60, a.avg300);
+        let resources = [
+            ("CPU", &self.cpu),
+            ("Memory", &self.memory),
+            ("I/O", &self.io),
+        ];
+        if resources.iter().all(|(_, psi)| psi.is_none()) {
+            return "Unavailable (no PSI)".to_string();
+        }
+        resources
+            .iter()
+            .filter_map(|(name, psi)| {
+                let psi = psi.as_ref()?;
+                Some(match &psi.full {
+                    Some(full) => {
+                        format!("{} {} (full {})", name, averages(&psi.some), averages(full))
+                    }
+                    None => format!("{} {}", name, averages(&psi.some)),
+                })
+            })
+            .collect::<Vec<_>>()
+            .join(" | ")
+    }
+}
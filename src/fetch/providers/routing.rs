@@ -0,0 +1,209 @@
+use std::fs;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+/// Stub listener address written into /etc/resolv.conf by systemd-resolved
+const RESOLVED_STUB: &str = "127.0.0.53";
+/// resolv.conf listing the upstream servers systemd-resolved forwards to
+const RESOLVED_UPSTREAM: &str = "/run/systemd/resolve/resolv.conf";
+
+/// RTF_UP from linux/route.h
+const RTF_UP: u32 = 0x0001;
+
+pub struct DefaultRoute {
+    pub interface: String,
+    /// None for point-to-point links (ppp, wireguard) that route without a next hop
+    pub gateway: Option<IpAddr>,
+    pub metric: u32,
+}
+
+impl DefaultRoute {
+    /// "192.168.1.1 via wlan0"
+    pub fn summary(&self) -> String {
+        match self.gateway {
+            Some(gateway) => format!("{} via {}", gateway, self.interface),
+            None => format!("dev {}", self.interface),
+        }
+    }
+}
+
+pub struct DnsInfo {
+    pub servers: Vec<String>,
+    pub search: Vec<String>,
+    /// Set when /etc/resolv.conf points at the systemd-resolved stub;
+    /// holds the servers it actually forwards to
+    pub resolved_upstream: Option<Vec<String>>,
+}
+
+impl DnsInfo {
+    /// "1.1.1.1, 9.9.9.9" or "127.0.0.53 (resolved: 1.1.1.1)"
+    pub fn summary(&self) -> String {
+        if self.servers.is_empty() {
+            return "None".to_string();
+        }
+        let servers = self.servers.join(", ");
+        match &self.resolved_upstream {
+            Some(upstream) if !upstream.is_empty() => {
+                format!("{} (resolved: {})", servers, upstream.join(", "))
+            }
+            Some(_) => format!("{} (resolved)", servers),
+            None => servers,
+        }
+    }
+}
+
+pub struct RoutingInfo {
+    /// IPv4 default routes, lowest metric first
+    pub ipv4: Vec<DefaultRoute>,
+    /// IPv6 default routes, lowest metric first
+    pub ipv6: Vec<DefaultRoute>,
+    pub dns: DnsInfo,
+}
+
+impl Default for RoutingInfo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RoutingInfo {
+    pub fn new() -> Self {
+        Self {
+            ipv4: Self::read_ipv4_routes(),
+            ipv6: Self::read_ipv6_routes(),
+            dns: Self::read_dns(),
+        }
+    }
+
+    /// Interface carrying the preferred default route (IPv4 first)
+    pub fn uplink(&self) -> Option<&str> {
+        self.ipv4
+            .first()
+            .or_else(|| self.ipv6.first())
+            .map(|r| r.interface.as_str())
+    }
+
+    pub fn get_formatted_gateway(&self) -> String {
+        let routes: Vec<String> = self
+            .ipv4
+            .iter()
+            .chain(self.ipv6.iter())
+            .map(|r| r.summary())
+            .collect();
+        if routes.is_empty() {
+            "None".to_string()
+        } else {
+            routes.join(", ")
+        }
+    }
+
+    /// Parses /proc/net/route. Addresses are hex dumps of the network-order
+    /// bytes, so they read back correctly with the native byte order:
+    /// Iface  Destination  Gateway   Flags  RefCnt  Use  Metric  Mask      ...
+    /// wlan0  00000000     0101A8C0  0003   0       0    600     00000000  ...
+    fn read_ipv4_routes() -> Vec<DefaultRoute> {
+        let Ok(content) = fs::read_to_string("/proc/net/route") else {
+            return Vec::new();
+        };
+
+        let hex = |s: &str| u32::from_str_radix(s, 16).ok();
+        let mut routes: Vec<DefaultRoute> = content
+            .lines()
+            .skip(1)
+            .filter_map(|line| {
+                let fields: Vec<&str> = line.split_whitespace().collect();
+                if fields.len() < 8 {
+                    return None;
+                }
+                let flags = hex(fields[3])?;
+                if hex(fields[1])? != 0 || hex(fields[7])? != 0 || flags & RTF_UP == 0 {
+                    return None;
+                }
+                let gateway = hex(fields[2])?;
+                Some(DefaultRoute {
+                    interface: fields[0].to_string(),
+                    gateway: (gateway != 0)
+                        .then(|| IpAddr::V4(Ipv4Addr::from(gateway.to_ne_bytes()))),
+                    metric: fields[6].parse().unwrap_or(0),
+                })
+            })
+            .collect();
+
+        routes.sort_by_key(|r| r.metric);
+        routes
+    }
+
+    /// Parses /proc/net/ipv6_route:
+    /// dest prefix src prefix next_hop metric refcnt use flags iface
+    /// (addresses as 32 hex digits, metric and flags in hex)
+    fn read_ipv6_routes() -> Vec<DefaultRoute> {
+        let Ok(content) = fs::read_to_string("/proc/net/ipv6_route") else {
+            return Vec::new();
+        };
+
+        let mut routes: Vec<DefaultRoute> = content
+            .lines()
+            .filter_map(|line| {
+                let fields: Vec<&str> = line.split_whitespace().collect();
+                if fields.len() < 10 {
+                    return None;
+                }
+                let flags = u32::from_str_radix(fields[8], 16).ok()?;
+                // "lo" carries the kernel's unreachable catch-all routes
+                if fields[9] == "lo" || flags & RTF_UP == 0 {
+                    return None;
+                }
+                if u128::from_str_radix(fields[0], 16).ok()? != 0 || fields[1] != "00" {
+                    return None;
+                }
+                let next_hop = u128::from_str_radix(fields[4], 16).ok()?;
+                Some(DefaultRoute {
+                    interface: fields[9].to_string(),
+                    gateway: (next_hop != 0).then(|| IpAddr::V6(Ipv6Addr::from(next_hop))),
+                    metric: u32::from_str_radix(fields[5], 16).unwrap_or(0),
+                })
+            })
+            .collect();
+
+        routes.sort_by_key(|r| r.metric);
+        routes
+    }
+
+    fn read_dns() -> DnsInfo {
+        let (servers, search) = Self::parse_resolv_conf("/etc/resolv.conf");
+
+        // Either the stub address or a symlink to .../stub-resolv.conf gives resolved away
+        let uses_stub = servers.iter().any(|s| s == RESOLVED_STUB)
+            || fs::read_link("/etc/resolv.conf")
+                .map(|target| target.to_string_lossy().ends_with("stub-resolv.conf"))
+                .unwrap_or(false);
+        let resolved_upstream = uses_stub.then(|| Self::parse_resolv_conf(RESOLVED_UPSTREAM).0);
+
+        DnsInfo {
+            servers,
+            search,
+            resolved_upstream,
+        }
+    }
+
+    /// Returns (nameservers, search domains). "domain" is the older single-entry form of "search".
+    fn parse_resolv_conf(path: &str) -> (Vec<String>, Vec<String>) {
+        let mut servers = Vec::new();
+        let mut search = Vec::new();
+        let Ok(content) = fs::read_to_string(path) else {
+            return (servers, search);
+        };
+
+        for line in content.lines() {
+            let mut parts = line.split_whitespace();
+            match parts.next() {
+                Some("nameserver") => servers.extend(parts.next().map(|s| s.to_string())),
+                // The last search/domain line wins, as in the resolver
+                Some("search") | Some("domain") => {
+                    search = parts.map(|s| s.to_string()).collect();
+                }
+                _ => {}
+            }
+        }
+        (servers, search)
+    }
+}
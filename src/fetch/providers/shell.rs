@@ -0,0 +1,287 @@
+use super::ancestry::Process;
+use super::version::cached_probe_version;
+use crate::config::ShellConfig;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Known shells: process name, display name and the flag that prints the
+/// version. dash and mksh have no such flag.
+const SHELLS: [(&str, &str, Option<&str>); 15] = [
+    ("bash", "bash", Some("--version")),
+    ("zsh", "zsh", Some("--version")),
+    ("fish", "fish", Some("--version")),
+    ("nu", "nushell", Some("--version")),
+    ("elvish", "elvish", Some("--version")),
+    ("xonsh", "xonsh", Some("--version")),
+    ("pwsh", "PowerShell", Some("--version")),
+    ("ion", "ion", Some("--version")),
+    ("tcsh", "tcsh", Some("--version")),
+    ("csh", "csh", None),
+    ("ksh", "ksh", Some("--version")),
+    ("mksh", "mksh", None),
+    ("yash", "yash", Some("--version")),
+    ("dash", "dash", None),
+    ("sh", "sh", None),
+];
+
+pub struct ShellInfo {
+    pub name: String,
+    pub version: Option<String>,
+    /// Prompt themes and plugin managers, e.g. ["Powerlevel10k", "Oh My Zsh"]
+    pub prompt: Vec<&'static str>,
+    pub plugins: Option<usize>,
+}
+
+impl ShellInfo {
+    /// The shell mizu was started from, found by walking up the process tree
+    /// (past sudo, cargo and the like). $SHELL is only the login shell and is
+    /// used when the walk finds nothing.
+    pub fn new(config: &ShellConfig) -> Self {
+        let found = Self::from_ancestry().or_else(|| {
+            let path = env::var("SHELL").ok()?;
+            let name = Path::new(&path).file_name()?.to_string_lossy().to_string();
+            Some((name, Some(path)))
+        });
+        let Some((proc_name, exe)) = found else {
+            return Self {
+                name: "Unknown".to_string(),
+                version: None,
+                prompt: Vec::new(),
+                plugins: None,
+            };
+        };
+
+        let known = SHELLS.iter().find(|(name, _, _)| *name == proc_name);
+        let name = known.map_or(proc_name.clone(), |(_, pretty, _)| pretty.to_string());
+        let version = known
+            .and_then(|(_, _, flag)| *flag)
+            .zip(exe)
+            .and_then(|(flag, exe)| cached_probe_version(&exe, &[flag]));
+
+        // Reading every rc file (and fish's conf.d) is only worth it when asked for
+        let rc = if config.show_prompt || config.show_plugins {
+            Self::rc_contents(&proc_name)
+        } else {
+            String::new()
+        };
+        Self {
+            name,
+            version,
+            prompt: if config.show_prompt {
+                Self::detect_prompt(&proc_name, &rc)
+            } else {
+                Vec::new()
+            },
+            plugins: config
+                .show_plugins
+                .then(|| Self::count_plugins(&proc_name, &rc))
+                .flatten(),
+        }
+    }
+
+    /// "zsh 5.9 (Powerlevel10k, Oh My Zsh, 12 plugins)"
+    pub fn summary(&self) -> String {
+        let mut out = match &self.version {
+            Some(version) => format!("{} {}", self.name, version),
+            None => self.name.clone(),
+        };
+        let mut extras: Vec<String> = self.prompt.iter().map(|p| p.to_string()).collect();
+        if let Some(count) = self.plugins {
+            extras.push(format!(
+                "{} plugin{}",
+                count,
+                if count == 1 { "" } else { "s" }
+            ));
+        }
+        if !extras.is_empty() {
+            out.push_str(&format!(" ({})", extras.join(", ")));
+        }
+        out
+    }
+
+    /// First known shell among our ancestors, with its executable
+    fn from_ancestry() -> Option<(String, Option<String>)> {
+        let mut pid = Process::read(std::process::id())?.ppid;
+        // Bounded in case of a weird /proc
+        for _ in 0..16 {
+            if pid <= 1 {
+                break;
+            }
+            let process = Process::read(pid)?;
+            // Login shells show up as "-zsh"
+            let name = process.name.trim_start_matches('-');
+            if SHELLS.iter().any(|(shell, _, _)| *shell == name) {
+                return Some((name.to_string(), process.exe));
+            }
+            pid = process.ppid;
+        }
+        None
+    }
+
+    /// The interactive rc file(s) prompts and plugins are configured in
+    fn rc_contents(shell: &str) -> String {
+        let Some(home) = dirs::home_dir() else {
+            return String::new();
+        };
+        let files: Vec<PathBuf> = match shell {
+            "zsh" => {
+                let zdotdir = env::var_os("ZDOTDIR").map_or(home.clone(), PathBuf::from);
+                vec![zdotdir.join(".zshrc")]
+            }
+            "bash" => vec![home.join(".bashrc")],
+            "fish" => {
+                let dir = dirs::config_dir()
+                    .unwrap_or(home.join(".config"))
+                    .join("fish");
+                let mut files = vec![dir.join("config.fish")];
+                if let Ok(entries) = fs::read_dir(dir.join("conf.d")) {
+                    files.extend(entries.flatten().map(|e| e.path()));
+                }
+                files
+            }
+            _ => Vec::new(),
+        };
+        files
+            .iter()
+            .filter_map(|f| fs::read_to_string(f).ok())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Env markers the frameworks export, then what the rc file loads
+    fn detect_prompt(shell: &str, rc: &str) -> Vec<&'static str> {
+        // Ignore commented-out lines so a disabled framework doesn't count
+        let loads = |needle: &str| {
+            rc.lines()
+                .any(|l| !l.trim_start().starts_with('#') && l.contains(needle))
+        };
+        let set = |var: &str| env::var_os(var).is_some();
+        let home = dirs::home_dir().unwrap_or_default();
+
+        let mut found = Vec::new();
+        if set("STARSHIP_SHELL") || loads("starship init") {
+            found.push("Starship");
+        }
+        if set("POSH_THEME") || loads("oh-my-posh") {
+            found.push("Oh My Posh");
+        }
+        match shell {
+            "zsh" => {
+                if loads("powerlevel10k") || loads("p10k") {
+                    found.push("Powerlevel10k");
+                }
+                if loads("prompt pure") || loads("sindresorhus/pure") {
+                    found.push("Pure");
+                }
+                if set("ZSH") || loads("oh-my-zsh.sh") {
+                    found.push("Oh My Zsh");
+                }
+                if loads("zprezto") {
+                    found.push("Prezto");
+                }
+                if loads("zinit") {
+                    found.push("zinit");
+                }
+                if loads("antidote") {
+                    found.push("antidote");
+                }
+            }
+            "bash" => {
+                if set("OSH") || loads("oh-my-bash.sh") {
+                    found.push("Oh My Bash");
+                }
+                if set("BASH_IT") || loads("bash_it.sh") {
+                    found.push("Bash-it");
+                }
+            }
+            "fish" => {
+                let functions = dirs::config_dir()
+                    .unwrap_or(home.join(".config"))
+                    .join("fish/functions");
+                if functions.join("_tide_item_os.fish").exists() {
+                    found.push("Tide");
+                }
+                if set("OMF_PATH") {
+                    found.push("Oh My Fish");
+                }
+            }
+            _ => {}
+        }
+        found
+    }
+
+    /// Plugins listed by the plugin manager in use: the oh-my-zsh/oh-my-bash
+    /// `plugins=(...)` array, zinit/zplug declarations, antidote's and
+    /// fisher's plugin files
+    fn count_plugins(shell: &str, rc: &str) -> Option<usize> {
+        let home = dirs::home_dir()?;
+        let non_comment_lines = |path: &Path| {
+            fs::read_to_string(path).ok().map(|content| {
+                content
+                    .lines()
+                    .map(str::trim)
+                    .filter(|l| !l.is_empty() && !l.starts_with('#'))
+                    .count()
+            })
+        };
+
+        match shell {
+            "zsh" | "bash" => {
+                let mut count = None;
+                // May span several lines: plugins=(\n  git\n  docker\n)
+                if let Some(start) = rc
+                    .lines()
+                    .position(|l| l.trim_start().starts_with("plugins=("))
+                {
+                    let array: String = rc
+                        .lines()
+                        .skip(start)
+                        .scan(false, |done, line| {
+                            (!*done).then(|| {
+                                *done = line.contains(')');
+                                line.split('#').next().unwrap_or("").to_string()
+                            })
+                        })
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    let inner = array
+                        .trim_start()
+                        .trim_start_matches("plugins=(")
+                        .split(')')
+                        .next()
+                        .unwrap_or("");
+                    count = Some(inner.split_whitespace().count());
+                }
+                let declared = rc
+                    .lines()
+                    .map(str::trim_start)
+                    .filter(|l| {
+                        l.starts_with("zinit light ")
+                            || l.starts_with("zinit load ")
+                            // zplug "user/repo", not `zplug check` / `zplug load`
+                            || l
+                                .strip_prefix("zplug ")
+                                .is_some_and(|rest| rest.trim_start().starts_with(['"', '\'']))
+                    })
+                    .count();
+                if declared > 0 {
+                    count = Some(count.unwrap_or(0) + declared);
+                }
+                if shell == "zsh" {
+                    let zdotdir = env::var_os("ZDOTDIR").map_or(home.clone(), PathBuf::from);
+                    if let Some(antidote) = non_comment_lines(&zdotdir.join(".zsh_plugins.txt")) {
+                        count = Some(count.unwrap_or(0) + antidote);
+                    }
+                }
+                count
+            }
+            "fish" => non_comment_lines(
+                &dirs::config_dir()
+                    .unwrap_or(home.join(".config"))
+                    .join("fish/fish_plugins"),
+            ),
+            _ => None,
+        }
+    }
+}
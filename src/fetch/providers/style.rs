@@ -1,7 +1,8 @@
 use std::env;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+#[derive(Default)]
 pub struct StyleInfo {
     pub theme: String,
     pub icons: String,
@@ -10,96 +11,278 @@ pub struct StyleInfo {
     pub wm_theme: String,
 }
 
+/// What one configuration source sets
+#[derive(Default)]
+struct Settings {
+    theme: Option<String>,
+    icons: Option<String>,
+    font: Option<String>,
+    cursor: Option<String>,
+}
+
 impl StyleInfo {
-    pub fn new(_sys: &sysinfo::System) -> Self {
-        // Detect DE first
+    /// Reads every toolkit and X/Wayland source there is and labels each value
+    /// with where it came from: "Breeze [Qt], Adwaita-dark [GTK3/4], Adwaita [GTK2]"
+    pub fn new() -> Self {
         let de = env::var("XDG_CURRENT_DESKTOP")
             .or_else(|_| env::var("DESKTOP_SESSION"))
             .unwrap_or_default()
             .to_lowercase();
+        let is_kde = de.contains("kde") || de.contains("plasma");
+        let home = dirs::home_dir().unwrap_or_default();
+        let config = dirs::config_dir().unwrap_or_else(|| home.join(".config"));
 
-        if de.contains("kde") || de.contains("plasma") {
-            return Self::get_kde_style();
-        } else if de.contains("gnome") || de.contains("gtk") {
-            return Self::get_gtk_style();
+        let mut sources: Vec<(&str, Settings)> = Vec::new();
+        if is_kde {
+            sources.push(("Qt", Self::kde_settings(&config)));
+        }
+        sources.push(("GTK2", Self::gtk2_settings(&home)));
+        for (label, dir) in [("GTK3", "gtk-3.0"), ("GTK4", "gtk-4.0")] {
+            sources.push((
+                label,
+                Self::gtk_settings(&config.join(dir).join("settings.ini")),
+            ));
         }
+        for name in ["qt5ct", "qt6ct"] {
+            sources.push((
+                name,
+                Self::qtct_settings(&config.join(name).join(format!("{}.conf", name))),
+            ));
+        }
+        sources.push(("XSettings", Self::xsettingsd_settings(&home, &config)));
+        sources.push(("Xresources", Self::xresources_settings(&home)));
+        sources.push(("index.theme", Self::index_theme_settings(&home)));
+        // Hyprland and Sway export these for the cursor theme they apply
+        sources.push((
+            "Hyprcursor",
+            Settings {
+                cursor: env::var("HYPRCURSOR_THEME").ok(),
+                ..Default::default()
+            },
+        ));
+        sources.push((
+            "Xcursor",
+            Settings {
+                cursor: env::var("XCURSOR_THEME").ok(),
+                ..Default::default()
+            },
+        ));
+
+        // Desktop defaults for when the user never changed anything
+        let (default_theme, default_icons, default_font, default_cursor) = if de.contains("gnome") {
+            (
+                "Adwaita [GTK3/4]",
+                "Adwaita [GTK3/4]",
+                "Cantarell (11pt) [GTK3/4]",
+                "Adwaita [GTK3/4]",
+            )
+        } else {
+            ("Unknown", "Unknown", "Unknown", "Unknown")
+        };
+        let or_default =
+            |value: Option<String>, default: &str| value.unwrap_or(default.to_string());
 
-        // Fallback or generic
         Self {
-            theme: "Unknown".to_string(),
-            icons: "Unknown".to_string(),
-            font: "Unknown".to_string(),
-            cursor: "Unknown".to_string(),
-            wm_theme: "Unknown".to_string(),
+            theme: or_default(Self::combine(&sources, |s| &s.theme), default_theme),
+            icons: or_default(Self::combine(&sources, |s| &s.icons), default_icons),
+            font: or_default(Self::combine(&sources, |s| &s.font), default_font),
+            cursor: or_default(Self::combine(&sources, |s| &s.cursor), default_cursor),
+            wm_theme: if is_kde {
+                Self::parse_ini(&config.join("kwinrc"), "org.kde.kdecoration2", "theme")
+                    .unwrap_or_else(|| "Breeze".to_string())
+            } else {
+                "Unknown".to_string()
+            },
         }
     }
 
-    fn get_kde_style() -> Self {
-        let home = env::var("HOME").unwrap_or_default();
-        let kdeglobals = Path::new(&home).join(".config/kdeglobals");
-        let kcminputrc = Path::new(&home).join(".config/kcminputrc");
-        let kwinrc = Path::new(&home).join(".config/kwinrc");
+    /// Groups sources that agree on a value: "Adwaita [GTK2/3/4], Breeze [qt5ct, XSettings]"
+    fn combine(
+        sources: &[(&str, Settings)],
+        field: fn(&Settings) -> &Option<String>,
+    ) -> Option<String> {
+        let mut groups: Vec<(&str, Vec<&str>)> = Vec::new();
+        for (label, settings) in sources {
+            let Some(value) = field(settings).as_deref().filter(|v| !v.is_empty()) else {
+                continue;
+            };
+            match groups.iter_mut().find(|(v, _)| *v == value) {
+                Some((_, labels)) => labels.push(label),
+                None => groups.push((value, vec![label])),
+            }
+        }
+        if groups.is_empty() {
+            return None;
+        }
+
+        let formatted: Vec<String> = groups
+            .iter()
+            .map(|(value, labels)| {
+                // "GTK2", "GTK3" -> "GTK2/3"
+                let gtk: Vec<&str> = labels
+                    .iter()
+                    .filter_map(|l| l.strip_prefix("GTK"))
+                    .collect();
+                let mut parts: Vec<String> = labels
+                    .iter()
+                    .filter(|l| !l.starts_with("GTK"))
+                    .map(|l| l.to_string())
+                    .collect();
+                if !gtk.is_empty() {
+                    let position = labels
+                        .iter()
+                        .position(|l| l.starts_with("GTK"))
+                        .unwrap_or(0);
+                    parts.insert(position, format!("GTK{}", gtk.join("/")));
+                }
+                format!("{} [{}]", value, parts.join(", "))
+            })
+            .collect();
+        Some(formatted.join(", "))
+    }
 
+    /// Plasma leaves keys out while they're at their default
+    fn kde_settings(config: &Path) -> Settings {
+        let kdeglobals = config.join("kdeglobals");
         let theme = Self::parse_ini(&kdeglobals, "General", "ColorScheme")
             .or_else(|| Self::parse_ini(&kdeglobals, "KDE", "widgetStyle"))
             .unwrap_or_else(|| "Breeze".to_string());
-
         let icons =
             Self::parse_ini(&kdeglobals, "Icons", "Theme").unwrap_or_else(|| "breeze".to_string());
-
         let font = Self::parse_ini(&kdeglobals, "General", "font")
-            .unwrap_or_else(|| "Noto Sans,10,-1,5,50,0,0,0,0,0".to_string()); // Raw KDE font string
-
-        // Clean up KDE font string: "Noto Sans,10,-1,..." -> "Noto Sans (10pt)"
-        let clean_font = if let Some(comma_idx) = font.find(',') {
-            let name = &font[..comma_idx];
-            let rest = &font[comma_idx + 1..];
-            let size = rest.split(',').next().unwrap_or("10");
-            format!("{} ({}pt)", name, size)
-        } else {
-            font
-        };
-
-        let cursor = Self::parse_ini(&kcminputrc, "Mouse", "cursorTheme")
+            .map(|f| Self::clean_qt_font(&f))
+            .unwrap_or_else(|| "Noto Sans (10pt)".to_string());
+        let cursor = Self::parse_ini(&config.join("kcminputrc"), "Mouse", "cursorTheme")
             .unwrap_or_else(|| "breeze_cursors".to_string());
 
-        let wm_theme = Self::parse_ini(&kwinrc, "org.kde.kdecoration2", "theme")
-            .unwrap_or_else(|| "Breeze".to_string());
+        Settings {
+            theme: Some(theme),
+            icons: Some(icons),
+            font: Some(font),
+            cursor: Some(cursor),
+        }
+    }
 
-        Self {
-            theme: format!("{} [Qt], Breeze [GTK2/3]", theme), // Simplify GTK part for now
-            icons,
-            font: clean_font,
-            cursor,
-            wm_theme,
+    /// ~/.gtkrc-2.0 (or $GTK2_RC_FILES): `gtk-theme-name="Adwaita"` lines
+    fn gtk2_settings(home: &Path) -> Settings {
+        let path = env::var_os("GTK2_RC_FILES")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| home.join(".gtkrc-2.0"));
+        let content = fs::read_to_string(path).unwrap_or_default();
+        let get = |key| Self::parse_value(&content, key, '=');
+
+        Settings {
+            theme: get("gtk-theme-name"),
+            icons: get("gtk-icon-theme-name"),
+            font: get("gtk-font-name").map(|f| Self::clean_gtk_font(&f)),
+            cursor: get("gtk-cursor-theme-name"),
+        }
+    }
+
+    /// gtk-3.0 and gtk-4.0 settings.ini
+    fn gtk_settings(path: &Path) -> Settings {
+        let get = |key| Self::parse_ini(path, "Settings", key);
+        Settings {
+            theme: get("gtk-theme-name"),
+            icons: get("gtk-icon-theme-name"),
+            font: get("gtk-font-name").map(|f| Self::clean_gtk_font(&f)),
+            cursor: get("gtk-cursor-theme-name"),
         }
     }
 
-    fn get_gtk_style() -> Self {
-        let home = env::var("HOME").unwrap_or_default();
-        let gtk3_config = Path::new(&home).join(".config/gtk-3.0/settings.ini");
+    /// qt5ct.conf / qt6ct.conf; the style is the closest thing Qt has to a theme
+    fn qtct_settings(path: &Path) -> Settings {
+        Settings {
+            theme: Self::parse_ini(path, "Appearance", "style"),
+            icons: Self::parse_ini(path, "Appearance", "icon_theme"),
+            // qt6ct may store a binary @Variant(...) instead of the font string
+            font: Self::parse_ini(path, "Fonts", "general")
+                .filter(|f| !f.starts_with("@Variant"))
+                .map(|f| Self::clean_qt_font(f.trim_matches('"'))),
+            cursor: None,
+        }
+    }
 
-        let theme = Self::parse_ini(&gtk3_config, "Settings", "gtk-theme-name")
-            .unwrap_or_else(|| "Adwaita".to_string());
+    /// xsettingsd config: `Net/ThemeName "Adwaita"` lines
+    fn xsettingsd_settings(home: &Path, config: &Path) -> Settings {
+        let content = [
+            config.join("xsettingsd/xsettingsd.conf"),
+            home.join(".xsettingsd"),
+        ]
+        .iter()
+        .find_map(|path| fs::read_to_string(path).ok())
+        .unwrap_or_default();
+        let get = |key| Self::parse_value(&content, key, ' ');
+
+        Settings {
+            theme: get("Net/ThemeName"),
+            icons: get("Net/IconThemeName"),
+            font: get("Gtk/FontName").map(|f| Self::clean_gtk_font(&f)),
+            cursor: get("Gtk/CursorThemeName"),
+        }
+    }
 
-        let icons = Self::parse_ini(&gtk3_config, "Settings", "gtk-icon-theme-name")
-            .unwrap_or_else(|| "Adwaita".to_string());
+    /// `Xcursor.theme: Bibata-Modern-Ice` in ~/.Xresources or ~/.Xdefaults
+    fn xresources_settings(home: &Path) -> Settings {
+        let content = [".Xresources", ".Xdefaults"]
+            .iter()
+            .find_map(|name| fs::read_to_string(home.join(name)).ok())
+            .unwrap_or_default();
+        Settings {
+            cursor: Self::parse_value(&content, "Xcursor.theme", ':'),
+            ..Default::default()
+        }
+    }
 
-        let font = Self::parse_ini(&gtk3_config, "Settings", "gtk-font-name")
-            .unwrap_or_else(|| "Cantarell 11".to_string());
+    /// The "default" icon theme only exists to point X at a cursor theme
+    fn index_theme_settings(home: &Path) -> Settings {
+        let path = [
+            ".icons/default/index.theme",
+            ".local/share/icons/default/index.theme",
+        ]
+        .iter()
+        .map(|p| home.join(p))
+        .find(|p| p.exists())
+        .unwrap_or_default();
+        Settings {
+            cursor: Self::parse_ini(&path, "Icon Theme", "Inherits"),
+            ..Default::default()
+        }
+    }
 
-        let cursor = Self::parse_ini(&gtk3_config, "Settings", "gtk-cursor-theme-name")
-            .unwrap_or_else(|| "Adwaita".to_string());
+    /// "Cantarell 11" -> "Cantarell (11pt)"
+    fn clean_gtk_font(font: &str) -> String {
+        match font.rsplit_once(' ') {
+            Some((name, size)) if size.parse::<f32>().is_ok() => format!("{} ({}pt)", name, size),
+            _ => font.to_string(),
+        }
+    }
 
-        Self {
-            theme,
-            icons,
-            font,
-            cursor,
-            wm_theme: "Unknown".to_string(), // GTK usually implies the WM theme matches or is hidden
+    /// "Noto Sans,10,-1,5,50,0,0,0,0,0" -> "Noto Sans (10pt)"
+    fn clean_qt_font(font: &str) -> String {
+        let mut parts = font.split(',');
+        match (parts.next(), parts.next()) {
+            (Some(name), Some(size)) => format!("{} ({}pt)", name, size),
+            _ => font.to_string(),
         }
     }
 
+    /// Section-less `key<sep>value` files, values optionally double-quoted.
+    /// A space separator means any run of whitespace.
+    fn parse_value(content: &str, key: &str, sep: char) -> Option<String> {
+        content.lines().find_map(|line| {
+            let line = line.trim();
+            let (k, v) = if sep == ' ' {
+                line.split_once(char::is_whitespace)?
+            } else {
+                line.split_once(sep)?
+            };
+            (k.trim() == key)
+                .then(|| v.trim().trim_matches('"').to_string())
+                .filter(|v| !v.is_empty())
+        })
+    }
+
     fn parse_ini(path: &Path, section: &str, key: &str) -> Option<String> {
         if let Ok(content) = fs::read_to_string(path) {
             let mut current_section = "";
@@ -107,12 +290,11 @@ impl StyleInfo {
                 let line = line.trim();
                 if line.starts_with('[') && line.ends_with(']') {
                     current_section = &line[1..line.len() - 1];
-                } else if current_section == section {
-                    if let Some((k, v)) = line.split_once('=') {
-                        if k.trim() == key {
-                            return Some(v.trim().to_string());
-                        }
-                    }
+                } else if current_section == section
+                    && let Some((k, v)) = line.split_once('=')
+                    && k.trim() == key
+                {
+                    return Some(v.trim().to_string());
                 }
             }
         }
@@ -0,0 +1,245 @@
+use super::ancestry::Process;
+use super::version::{cached_probe_version, run_with_timeout};
+use std::env;
+
+/// Processes between us and the terminal emulator that should be walked past
+const SKIPPED: [&str; 30] = [
+    "bash", "zsh", "fish", "sh", "dash", "ksh", "mksh", "tcsh", "csh", "nu", "xonsh", "elvish",
+    "pwsh", "oil", "yash", "sudo", "doas", "su", "run0", "login", "script", "env", "nohup", "time",
+    "strace", "cargo", "mizu", "tmux", "zellij", "screen",
+];
+
+/// Known emulators: process name, display name and the flag that prints the version.
+/// Unknown binaries are never executed, running them could open a window.
+const TERMINALS: [(&str, &str, Option<&str>); 30] = [
+    ("kitty", "kitty", Some("--version")),
+    ("alacritty", "Alacritty", Some("--version")),
+    ("wezterm-gui", "WezTerm", None),
+    ("wezterm", "WezTerm", Some("--version")),
+    ("ghostty", "Ghostty", Some("--version")),
+    ("foot", "foot", Some("--version")),
+    ("footclient", "foot", Some("--version")),
+    ("gnome-terminal-server", "GNOME Terminal", None),
+    ("gnome-terminal", "GNOME Terminal", Some("--version")),
+    ("kgx", "GNOME Console", Some("--version")),
+    ("ptyxis-agent", "Ptyxis", None),
+    ("ptyxis", "Ptyxis", Some("--version")),
+    ("konsole", "Konsole", Some("--version")),
+    ("yakuake", "Yakuake", Some("--version")),
+    ("xfce4-terminal", "Xfce Terminal", Some("--version")),
+    ("mate-terminal", "MATE Terminal", Some("--version")),
+    ("lxterminal", "LXTerminal", Some("--version")),
+    ("qterminal", "QTerminal", Some("--version")),
+    ("terminator", "Terminator", Some("--version")),
+    ("tilix", "Tilix", Some("--version")),
+    ("terminology", "Terminology", Some("--version")),
+    ("xterm", "xterm", Some("-version")),
+    ("urxvt", "URxvt", None),
+    ("urxvtd", "URxvt", None),
+    ("st", "st", Some("-v")),
+    ("rio", "Rio", Some("--version")),
+    ("contour", "Contour", Some("--version")),
+    ("blackbox", "Black Box", Some("--version")),
+    ("code", "VS Code", None),
+    ("sshd", "SSH", None),
+];
+
+pub struct TerminalInfo {
+    pub name: String,
+    pub version: Option<String>,
+}
+
+impl TerminalInfo {
+    /// "kitty 0.35.2"
+    pub fn summary(&self) -> String {
+        match &self.version {
+            Some(version) => format!("{} {}", self.name, version),
+            None => self.name.clone(),
+        }
+    }
+
+    /// Finds the terminal emulator by walking up from our parent process.
+    /// Inside tmux the shell descends from the tmux server, so the walk
+    /// continues from the attached client instead. Env hints cover the rest
+    /// (containers, zellij/screen, remote sessions).
+    pub fn detect(multiplexer: Option<&MultiplexerInfo>) -> Option<Self> {
+        let start = match multiplexer {
+            Some(mux) if mux.client_pid.is_some() => mux.client_pid,
+            // Without a client PID the walk would only find the multiplexer server
+            Some(_) => None,
+            None => Process::read(std::process::id()).map(|p| p.ppid),
+        };
+
+        let (known, unknown) = start.map(Self::walk).unwrap_or_default();
+        match known {
+            // Over SSH the "terminal" is sshd; the env may know the real one
+            // (some clients forward TERM_PROGRAM)
+            Some(term) if term.name == "SSH" => Self::from_env().or_else(|| {
+                let tty = env::var("SSH_TTY").unwrap_or_default();
+                Some(Self {
+                    name: if tty.is_empty() {
+                        "SSH".to_string()
+                    } else {
+                        format!("SSH ({})", tty)
+                    },
+                    version: None,
+                })
+            }),
+            Some(term) => Some(term),
+            // An emulator we don't know still beats nothing
+            None => Self::from_env().or(unknown.map(|name| Self {
+                name,
+                version: None,
+            })),
+        }
+    }
+
+    /// Returns the first known emulator among the ancestors, and otherwise the
+    /// first ancestor that isn't a shell or wrapper
+    fn walk(mut pid: u32) -> (Option<Self>, Option<String>) {
+        let mut unknown = None;
+        // Bounded in case of a weird /proc
+        for _ in 0..32 {
+            let Some(process) = (pid > 1).then(|| Process::read(pid)).flatten() else {
+                break;
+            };
+            if let Some(&(_, pretty, flag)) =
+                TERMINALS.iter().find(|(name, _, _)| *name == process.name)
+            {
+                let version = Self::env_version(pretty).or_else(|| {
+                    flag.and_then(|flag| cached_probe_version(process.exe.as_deref()?, &[flag]))
+                });
+                let term = Self {
+                    name: pretty.to_string(),
+                    version,
+                };
+                return (Some(term), unknown);
+            }
+            // systemd --user, session managers: we've left the terminal's subtree
+            if matches!(process.name.as_str(), "systemd" | "init") {
+                break;
+            }
+            if unknown.is_none() && !SKIPPED.contains(&process.name.as_str()) {
+                unknown = Some(process.name.clone());
+            }
+            pid = process.ppid;
+        }
+        (None, unknown)
+    }
+
+    /// Versions some terminals export, saving a process spawn
+    fn env_version(pretty: &str) -> Option<String> {
+        match pretty {
+            // "230802" -> "23.08.02"
+            "Konsole" => env::var("KONSOLE_VERSION")
+                .ok()
+                .filter(|v| v.len() == 6)
+                .map(|v| format!("{}.{}.{}", &v[0..2], &v[2..4], &v[4..6])),
+            "WezTerm" | "Ghostty" | "VS Code" => env::var("TERM_PROGRAM_VERSION").ok(),
+            _ => None,
+        }
+    }
+
+    /// Terminal-specific variables, then TERM_PROGRAM
+    fn from_env() -> Option<Self> {
+        let set = |var: &str| env::var_os(var).is_some();
+        let name = if set("KITTY_WINDOW_ID") {
+            "kitty"
+        } else if set("WEZTERM_PANE") {
+            "WezTerm"
+        } else if set("ALACRITTY_SOCKET") || set("ALACRITTY_WINDOW_ID") {
+            "Alacritty"
+        } else if set("GHOSTTY_RESOURCES_DIR") {
+            "Ghostty"
+        } else if set("KONSOLE_VERSION") {
+            "Konsole"
+        } else if set("GNOME_TERMINAL_SCREEN") {
+            "GNOME Terminal"
+        } else if set("TILIX_ID") {
+            "Tilix"
+        } else if set("TERMINATOR_UUID") {
+            "Terminator"
+        } else if set("WT_SESSION") {
+            "Windows Terminal"
+        } else {
+            return env::var("TERM_PROGRAM")
+                .ok()
+                .filter(|p| !p.is_empty() && p != "tmux" && p != "screen")
+                .map(|name| Self {
+                    name: match name.as_str() {
+                        "vscode" => "VS Code".to_string(),
+                        "Apple_Terminal" => "Apple Terminal".to_string(),
+                        _ => name,
+                    },
+                    version: env::var("TERM_PROGRAM_VERSION").ok(),
+                });
+        };
+
+        Some(Self {
+            name: name.to_string(),
+            version: Self::env_version(name),
+        })
+    }
+}
+
+pub struct MultiplexerInfo {
+    pub name: &'static str,
+    pub version: Option<String>,
+    pub session: Option<String>,
+    /// PID of the client attached to our session (tmux only)
+    pub client_pid: Option<u32>,
+}
+
+impl MultiplexerInfo {
+    pub fn detect() -> Option<Self> {
+        if env::var_os("TMUX").is_some() {
+            // One round trip for both the session name and the client's PID
+            let output = run_with_timeout("tmux", &["display-message", "-p", "#{client_pid} #S"])
+                .map(|(stdout, _)| stdout.trim().to_string())
+                .unwrap_or_default();
+            let (client_pid, session) = match output.split_once(' ') {
+                Some((pid, session)) => (pid.parse().ok(), Some(session.to_string())),
+                None => (None, None),
+            };
+            return Some(Self {
+                name: "tmux",
+                version: cached_probe_version("tmux", &["-V"]),
+                session,
+                client_pid,
+            });
+        }
+
+        if env::var_os("ZELLIJ").is_some() {
+            return Some(Self {
+                name: "zellij",
+                version: cached_probe_version("zellij", &["--version"]),
+                session: env::var("ZELLIJ_SESSION_NAME").ok(),
+                client_pid: None,
+            });
+        }
+
+        // STY is "<pid>.<session name>"
+        if let Ok(sty) = env::var("STY") {
+            return Some(Self {
+                name: "screen",
+                version: cached_probe_version("screen", &["-v"]),
+                session: sty.split_once('.').map(|(_, name)| name.to_string()),
+                client_pid: None,
+            });
+        }
+
+        None
+    }
+
+    /// "tmux 3.4 (session: main)"
+    pub fn summary(&self) -> String {
+        let mut out = self.name.to_string();
+        if let Some(version) = &self.version {
+            out.push_str(&format!(" {}", version));
+        }
+        if let Some(session) = &self.session {
+            out.push_str(&format!(" (session: {})", session));
+        }
+        out
+    }
+}
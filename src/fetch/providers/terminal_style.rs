@@ -0,0 +1,393 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Font and color theme configured in the terminal emulator itself, as
+/// opposed to the desktop UI font in StyleInfo.
+#[derive(Default)]
+pub struct TerminalStyle {
+    /// "JetBrains Mono (11pt)"
+    pub font: Option<String>,
+    /// Color scheme or theme name, e.g. "Catppuccin-Mocha"
+    pub theme: Option<String>,
+}
+
+impl TerminalStyle {
+    /// Reads the config of the terminal named by `OsInfo::terminal`
+    /// ("kitty 0.35.2", "GNOME Terminal", ...)
+    pub fn new(terminal: &str) -> Self {
+        let home = dirs::home_dir().unwrap_or_default();
+        let config = dirs::config_dir().unwrap_or_else(|| home.join(".config"));
+        let is = |name: &str| terminal.starts_with(name);
+
+        if is("kitty") {
+            Self::kitty(&config)
+        } else if is("Alacritty") {
+            Self::alacritty(&config)
+        } else if is("foot") {
+            Self::foot(&config)
+        } else if is("WezTerm") {
+            Self::wezterm(&home, &config)
+        } else if is("Ghostty") {
+            Self::ghostty(&config)
+        } else if is("Konsole") || is("Yakuake") {
+            Self::konsole(&home, &config)
+        } else if is("GNOME Terminal") {
+            Self::gnome_terminal()
+        } else {
+            Self::default()
+        }
+    }
+
+    /// "Name" + "11.0" -> "Name (11pt)"
+    fn font_with_size(family: Option<String>, size: Option<String>) -> Option<String> {
+        let family = family?;
+        Some(match size {
+            Some(size) => format!("{} ({}pt)", family, size.trim_end_matches(".0")),
+            None => family,
+        })
+    }
+
+    /// Theme names are usually the file a config includes: ".../themes/dracula.toml" -> "dracula"
+    fn file_stem(path: &str) -> Option<String> {
+        Path::new(path)
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+    }
+
+    fn expand_home(path: &str, base: &Path) -> PathBuf {
+        match path.strip_prefix("~/") {
+            Some(rest) => dirs::home_dir().unwrap_or_default().join(rest),
+            None => base.join(path),
+        }
+    }
+
+    /// kitty.conf, following `include`s.
+    /// The themes kitten writes the theme into an included file headed by "## name: ...".
+    fn kitty(config: &Path) -> Self {
+        let dir = env::var_os("KITTY_CONFIG_DIRECTORY")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| config.join("kitty"));
+
+        let mut conf = KittyConf::default();
+        conf.read(&dir.join("kitty.conf"), &dir, false);
+
+        Self {
+            // kitty's defaults
+            font: Self::font_with_size(
+                conf.family.or(Some("monospace".to_string())),
+                conf.size.or(Some("11".to_string())),
+            ),
+            theme: conf.theme,
+        }
+    }
+
+    /// alacritty.toml: [font] size and [font.normal] family, theme from the
+    /// imported file (`import` moved to [general] in 0.14)
+    fn alacritty(config: &Path) -> Self {
+        let dir = config.join("alacritty");
+        let Some(table) = fs::read_to_string(dir.join("alacritty.toml"))
+            .ok()
+            .and_then(|c| c.parse::<toml::Table>().ok())
+        else {
+            return Self::default();
+        };
+
+        let get = |table: &toml::Table, path: &[&str]| -> Option<toml::Value> {
+            let mut value = table.get(path[0])?;
+            for key in &path[1..] {
+                value = value.get(key)?;
+            }
+            Some(value.clone())
+        };
+        let imports: Vec<String> = get(&table, &["general", "import"])
+            .or_else(|| get(&table, &["import"]))
+            .and_then(|v| v.as_array().cloned())
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|v| v.as_str().map(str::to_string))
+            .collect();
+
+        // The main file overrides imports, so look there first
+        let mut tables = vec![table];
+        tables.extend(imports.iter().filter_map(|import| {
+            fs::read_to_string(Self::expand_home(import, &dir))
+                .ok()?
+                .parse::<toml::Table>()
+                .ok()
+        }));
+        let family = tables
+            .iter()
+            .find_map(|t| get(t, &["font", "normal", "family"]))
+            .and_then(|v| v.as_str().map(str::to_string));
+        let size = tables
+            .iter()
+            .find_map(|t| get(t, &["font", "size"]))
+            .map(|v| match v {
+                toml::Value::Float(f) => f.to_string(),
+                other => other.to_string(),
+            });
+
+        Self {
+            font: Self::font_with_size(family, size),
+            theme: imports.last().and_then(|i| Self::file_stem(i)),
+        }
+    }
+
+    /// foot.ini: `font=Fira Code:size=11` and `include=.../themes/dracula`
+    fn foot(config: &Path) -> Self {
+        let content = fs::read_to_string(config.join("foot/foot.ini")).unwrap_or_default();
+        let mut section = "main";
+        let mut font = None;
+        let mut theme = None;
+        for line in content.lines().map(str::trim) {
+            if line.starts_with('[') && line.ends_with(']') {
+                section = &line[1..line.len() - 1];
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            if section != "main" {
+                continue;
+            }
+            match key.trim() {
+                // Comma-separated fallbacks; options follow the family after ':'
+                "font" => {
+                    let primary = value.split(',').next().unwrap_or("").trim();
+                    let mut parts = primary.split(':');
+                    let family = parts.next().map(str::to_string);
+                    let size = parts.find_map(|p| p.strip_prefix("size=").map(str::to_string));
+                    font = Self::font_with_size(family, size);
+                }
+                "include" => theme = Self::file_stem(value.trim()),
+                _ => {}
+            }
+        }
+
+        Self {
+            font: font.or(Some("monospace (8pt)".to_string())),
+            theme,
+        }
+    }
+
+    /// wezterm.lua is a program; match the common `wezterm.font("...")`,
+    /// `[config.]font_size = 12.0` and `color_scheme = "..."` forms
+    fn wezterm(home: &Path, config: &Path) -> Self {
+        let content = [
+            home.join(".wezterm.lua"),
+            config.join("wezterm/wezterm.lua"),
+        ]
+        .iter()
+        .find_map(|path| fs::read_to_string(path).ok())
+        .unwrap_or_default();
+        let lines: Vec<&str> = content
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.starts_with("--"))
+            .collect();
+
+        // First string literal after `needle`
+        let quoted_after = |needle: &str| {
+            lines.iter().find_map(|line| {
+                let rest = &line[line.find(needle)? + needle.len()..];
+                let start = rest.find(['"', '\''])?;
+                let quote = rest[start..].chars().next()?;
+                let rest = &rest[start + 1..];
+                Some(rest[..rest.find(quote)?].to_string())
+            })
+        };
+        let family = quoted_after("font_with_fallback").or_else(|| quoted_after("wezterm.font"));
+        // `font_size = 12.0` in a returned table, `config.font_size = 12.0` with config_builder
+        let size = lines.iter().find_map(|line| {
+            let (key, value) = line.split_once('=')?;
+            let key = key.trim();
+            let name = match key.rsplit_once('.') {
+                Some((table, name))
+                    if table.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') =>
+                {
+                    name
+                }
+                Some(_) => return None,
+                None => key,
+            };
+            let value = value.trim().trim_end_matches(',');
+            (name == "font_size" && value.parse::<f32>().is_ok()).then(|| value.to_string())
+        });
+
+        Self {
+            font: Self::font_with_size(family.or(Some("JetBrains Mono".to_string())), size),
+            theme: quoted_after("color_scheme"),
+        }
+    }
+
+    /// ~/.config/ghostty/config: `font-family = ...`, `font-size = ...`, `theme = ...`
+    fn ghostty(config: &Path) -> Self {
+        let content = fs::read_to_string(config.join("ghostty/config")).unwrap_or_default();
+        let get = |key: &str| {
+            content.lines().find_map(|line| {
+                let (k, v) = line.split_once('=')?;
+                (k.trim() == key).then(|| v.trim().trim_matches('"').to_string())
+            })
+        };
+
+        Self {
+            font: Self::font_with_size(get("font-family"), get("font-size")),
+            theme: get("theme"),
+        }
+    }
+
+    /// konsolerc names the default profile, whose [Appearance] holds the
+    /// font ("Hack,10,-1,...") and color scheme
+    fn konsole(home: &Path, config: &Path) -> Self {
+        let ini = |path: &Path, section: &str, key: &str| {
+            let content = fs::read_to_string(path).ok()?;
+            let mut current = "";
+            content.lines().map(str::trim).find_map(|line| {
+                if line.starts_with('[') && line.ends_with(']') {
+                    current = &line[1..line.len() - 1];
+                    return None;
+                }
+                let (k, v) = line.split_once('=')?;
+                (current == section && k.trim() == key).then(|| v.trim().to_string())
+            })
+        };
+
+        let Some(profile) = ini(&config.join("konsolerc"), "Desktop Entry", "DefaultProfile")
+        else {
+            // The built-in profile: system monospace font, Breeze colors
+            return Self {
+                font: None,
+                theme: Some("Breeze".to_string()),
+            };
+        };
+        let path = home.join(".local/share/konsole").join(&profile);
+        let font = ini(&path, "Appearance", "Font").map(|font| {
+            let mut parts = font.split(',');
+            Self::font_with_size(
+                parts.next().map(str::to_string),
+                parts.next().map(str::to_string),
+            )
+            .unwrap_or(font.clone())
+        });
+
+        Self {
+            font,
+            theme: ini(&path, "Appearance", "ColorScheme").or(Some("Breeze".to_string())),
+        }
+    }
+
+    /// Profiles live in dconf; the profile name doubles as the theme since
+    /// gnome-terminal stores palettes without a name
+    fn gnome_terminal() -> Self {
+        let dconf = |args: &[&str]| {
+            let output = Command::new("dconf").args(args).output().ok()?;
+            output
+                .status
+                .success()
+                .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        };
+        let unquote = |v: &str| v.trim().trim_matches('\'').to_string();
+
+        let Some(dump) = dconf(&["dump", "/org/gnome/terminal/legacy/profiles:/"]) else {
+            return Self::default();
+        };
+        // The default key sits in the root section: [/] default='<uuid>'
+        let mut section = String::new();
+        let mut default = None;
+        let mut profiles: Vec<(String, Vec<(String, String)>)> = Vec::new();
+        for line in dump.lines().map(str::trim) {
+            if line.starts_with('[') && line.ends_with(']') {
+                section = line[1..line.len() - 1].trim_start_matches(':').to_string();
+                profiles.push((section.clone(), Vec::new()));
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            if section == "/" && key == "default" {
+                default = Some(unquote(value));
+            } else if let Some((_, keys)) = profiles.last_mut() {
+                keys.push((key.to_string(), value.to_string()));
+            }
+        }
+
+        let profile = profiles
+            .iter()
+            .find(|(uuid, _)| Some(uuid) == default.as_ref())
+            .or_else(|| profiles.iter().find(|(uuid, _)| uuid != "/"));
+        let get = |key: &str| {
+            profile
+                .and_then(|(_, keys)| keys.iter().find(|(k, _)| k == key).map(|(_, v)| unquote(v)))
+        };
+
+        let font = if get("use-system-font").as_deref() == Some("false") {
+            get("font")
+        } else {
+            Command::new("gsettings")
+                .args(["get", "org.gnome.desktop.interface", "monospace-font-name"])
+                .output()
+                .ok()
+                .map(|o| unquote(&String::from_utf8_lossy(&o.stdout)))
+                .filter(|f| !f.is_empty())
+        };
+        // "Monospace 12" -> "Monospace (12pt)"
+        let font = font.map(|font| match font.rsplit_once(' ') {
+            Some((family, size)) if size.parse::<f32>().is_ok() => {
+                format!("{} ({}pt)", family, size)
+            }
+            _ => font,
+        });
+
+        Self {
+            font,
+            theme: get("visible-name"),
+        }
+    }
+}
+
+/// Settings collected while reading kitty.conf and its includes
+#[derive(Default)]
+struct KittyConf {
+    family: Option<String>,
+    size: Option<String>,
+    theme: Option<String>,
+    /// Files read so far, bounded against include cycles
+    visited: usize,
+}
+
+impl KittyConf {
+    /// Like kitty, an `include` is applied where it appears, so later lines override it
+    fn read(&mut self, path: &Path, dir: &Path, included: bool) {
+        if self.visited >= 16 {
+            return;
+        }
+        self.visited += 1;
+        let Ok(content) = fs::read_to_string(path) else {
+            return;
+        };
+        let base = path.parent().unwrap_or(dir).to_path_buf();
+        for line in content.lines().map(str::trim) {
+            if included && let Some(name) = line.strip_prefix("## name:") {
+                self.theme = Some(name.trim().to_string());
+                continue;
+            }
+            let Some((key, value)) = line.split_once(char::is_whitespace) else {
+                continue;
+            };
+            let value = value.trim();
+            match key {
+                "font_family" => self.family = Some(value.to_string()),
+                "font_size" => self.size = Some(value.to_string()),
+                "include" => {
+                    if self.theme.is_none() && value.contains("theme") {
+                        self.theme = TerminalStyle::file_stem(value);
+                    }
+                    self.read(&TerminalStyle::expand_home(value, &base), dir, true);
+                }
+                _ => {}
+            }
+        }
+    }
+}
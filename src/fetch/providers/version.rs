@@ -0,0 +1,108 @@
+use crate::fetch::cache::CacheManager;
+use std::env;
+use std::fs;
+use std::io::Read;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant, UNIX_EPOCH};
+
+/// Upper bound for a probe like `--version`; some tools are slow to start (kitty is Python)
+const PROBE_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Runs `program args` and returns its stdout and stderr. The child is killed
+/// if it doesn't exit within the timeout, so a misbehaving binary (or a wedged
+/// server it talks to) can't hang startup.
+pub fn run_with_timeout(program: &str, args: &[&str]) -> Option<(String, String)> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .ok()?;
+
+    let start = Instant::now();
+    loop {
+        match child.try_wait() {
+            Ok(Some(_)) => break,
+            Ok(None) if start.elapsed() < PROBE_TIMEOUT => thread::sleep(Duration::from_millis(10)),
+            _ => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return None;
+            }
+        }
+    }
+
+    let mut stdout = String::new();
+    child.stdout.take()?.read_to_string(&mut stdout).ok()?;
+    let mut stderr = String::new();
+    if let Some(mut pipe) = child.stderr.take() {
+        let _ = pipe.read_to_string(&mut stderr);
+    }
+    Some((stdout, stderr))
+}
+
+/// Runs `program args` and returns the first version-looking token of its
+/// output ("kitty 0.35.2 created by ..." -> "0.35.2")
+pub fn probe_version(program: &str, args: &[&str]) -> Option<String> {
+    // Some tools (screen, older bash builds) print their version to stderr
+    let (stdout, stderr) = run_with_timeout(program, args)?;
+    extract_version(&format!("{}\n{}", stdout, stderr))
+}
+
+/// `probe_version`, cached per binary and re-probed when its path or mtime
+/// changes, so startup doesn't spawn every terminal, shell and WM each time
+pub fn cached_probe_version(program: &str, args: &[&str]) -> Option<String> {
+    let Some(exe) = resolve_program(program) else {
+        return probe_version(program, args);
+    };
+    let name = exe.file_name()?.to_string_lossy().to_string();
+    let key = format!("version-{}", name);
+    let stamp = format!(
+        "{} {}",
+        exe.display(),
+        fs::metadata(&exe)
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map_or(0, |d| d.as_secs())
+    );
+
+    if let Some(cached) = CacheManager::read(&key)
+        && let Some((cached_stamp, version)) = cached.split_once('\n')
+        && cached_stamp == stamp
+    {
+        return Some(version.trim().to_string()).filter(|v| !v.is_empty());
+    }
+
+    let version = probe_version(&exe.to_string_lossy(), args);
+    CacheManager::write(
+        &key,
+        &format!("{}\n{}", stamp, version.as_deref().unwrap_or("")),
+    );
+    version
+}
+
+/// A path as is, a bare name looked up in $PATH
+fn resolve_program(program: &str) -> Option<PathBuf> {
+    if program.contains('/') {
+        return Some(PathBuf::from(program));
+    }
+    env::split_paths(&env::var_os("PATH")?)
+        .map(|dir| dir.join(program))
+        .find(|path| path.is_file())
+}
+
+/// First whitespace-separated token that starts with a digit and contains a dot
+/// or is all digits, trimmed of trailing punctuation and "(...)" suffixes
+pub fn extract_version(text: &str) -> Option<String> {
+    text.split(|c: char| c.is_whitespace() || c == ',' || c == '(' || c == ')')
+        .map(|token| token.trim_start_matches('v'))
+        .find(|token| {
+            token.starts_with(|c: char| c.is_ascii_digit())
+                && (token.contains('.') || token.chars().all(|c| c.is_ascii_digit()))
+        })
+        .map(|token| token.trim_end_matches(['.', ':', ';']).to_string())
+}
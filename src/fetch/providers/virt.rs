@@ -0,0 +1,241 @@
+use std::fs;
+use std::path::Path;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Hypervisor {
+    Kvm,
+    /// QEMU without hardware acceleration (TCG)
+    Qemu,
+    Vmware,
+    VirtualBox,
+    HyperV,
+    Xen,
+    Parallels,
+    Bhyve,
+    AmazonEc2,
+    GoogleCompute,
+    /// The CPU reports a hypervisor but nothing identifies which one
+    Unknown,
+}
+
+impl Hypervisor {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Kvm => "KVM",
+            Self::Qemu => "QEMU",
+            Self::Vmware => "VMware",
+            Self::VirtualBox => "VirtualBox",
+            Self::HyperV => "Hyper-V",
+            Self::Xen => "Xen",
+            Self::Parallels => "Parallels",
+            Self::Bhyve => "bhyve",
+            Self::AmazonEc2 => "Amazon EC2",
+            Self::GoogleCompute => "Google Compute Engine",
+            Self::Unknown => "Virtual machine",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum ContainerKind {
+    Docker,
+    Podman,
+    Lxc,
+    SystemdNspawn,
+    Kubernetes,
+    Wsl,
+    Other,
+}
+
+impl ContainerKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Docker => "Docker",
+            Self::Podman => "Podman",
+            Self::Lxc => "LXC",
+            Self::SystemdNspawn => "systemd-nspawn",
+            Self::Kubernetes => "Kubernetes",
+            Self::Wsl => "WSL",
+            Self::Other => "Unknown",
+        }
+    }
+}
+
+pub struct ContainerInfo {
+    pub kind: ContainerKind,
+    /// Wrapper managing the container, e.g. "toolbox" or "distrobox"
+    pub tool: Option<String>,
+}
+
+/// What the system is running on: a hypervisor, a container, both or neither.
+#[derive(Default)]
+pub struct VirtInfo {
+    pub hypervisor: Option<Hypervisor>,
+    pub container: Option<ContainerInfo>,
+}
+
+impl VirtInfo {
+    pub fn new() -> Self {
+        Self {
+            hypervisor: Self::detect_hypervisor(),
+            container: Self::detect_container(),
+        }
+    }
+
+    /// WSL runs its own kernel in a VM, so it doesn't count here
+    pub fn in_container(&self) -> bool {
+        self.container
+            .as_ref()
+            .is_some_and(|c| c.kind != ContainerKind::Wsl)
+    }
+
+    /// "KVM guest", "Podman container (toolbox)", "Docker container in KVM guest"
+    pub fn get_formatted(&self) -> String {
+        let guest = self.hypervisor.map(|h| match h {
+            Hypervisor::Unknown => h.label().to_string(),
+            _ => format!("{} guest", h.label()),
+        });
+        let container = self.container.as_ref().map(|c| {
+            let mut out = match c.kind {
+                ContainerKind::Wsl => "WSL".to_string(),
+                _ => format!("{} container", c.kind.label()),
+            };
+            if let Some(tool) = &c.tool {
+                out.push_str(&format!(" ({})", tool));
+            }
+            out
+        });
+
+        match (container, guest) {
+            (Some(container), Some(guest)) => format!("{} in {}", container, guest),
+            (Some(container), None) => container,
+            (None, Some(guest)) => guest,
+            (None, None) => "Bare metal".to_string(),
+        }
+    }
+
+    fn detect_hypervisor() -> Option<Hypervisor> {
+        let cpu_flag = fs::read_to_string("/proc/cpuinfo")
+            .map(|info| {
+                info.lines()
+                    .find(|l| l.starts_with("flags"))
+                    .is_some_and(|l| l.split_whitespace().any(|f| f == "hypervisor"))
+            })
+            .unwrap_or(false);
+
+        // DMI strings identify the platform even when the CPU flag is hidden
+        let dmi: String = ["sys_vendor", "product_name", "board_vendor", "bios_vendor"]
+            .iter()
+            .filter_map(|f| fs::read_to_string(Path::new("/sys/class/dmi/id").join(f)).ok())
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_lowercase();
+
+        let from_dmi = if dmi.contains("qemu") || dmi.contains("kvm") {
+            // QEMU without KVM doesn't advertise the hypervisor bit
+            Some(if cpu_flag {
+                Hypervisor::Kvm
+            } else {
+                Hypervisor::Qemu
+            })
+        } else if dmi.contains("vmware") {
+            Some(Hypervisor::Vmware)
+        } else if dmi.contains("virtualbox") || dmi.contains("innotek") {
+            Some(Hypervisor::VirtualBox)
+        } else if dmi.contains("microsoft corporation") && dmi.contains("virtual machine") {
+            Some(Hypervisor::HyperV)
+        } else if dmi.contains("xen") {
+            Some(Hypervisor::Xen)
+        } else if dmi.contains("parallels") {
+            Some(Hypervisor::Parallels)
+        } else if dmi.contains("bhyve") {
+            Some(Hypervisor::Bhyve)
+        } else if dmi.contains("amazon ec2") {
+            Some(Hypervisor::AmazonEc2)
+        } else if dmi.contains("google compute engine") {
+            Some(Hypervisor::GoogleCompute)
+        } else {
+            None
+        };
+
+        from_dmi
+            .or_else(|| {
+                // Xen PV guests have no DMI at all
+                fs::read_to_string("/sys/hypervisor/type")
+                    .ok()
+                    .filter(|t| t.trim() == "xen")
+                    .map(|_| Hypervisor::Xen)
+            })
+            .or(cpu_flag.then_some(Hypervisor::Unknown))
+    }
+
+    fn detect_container() -> Option<ContainerInfo> {
+        let tool = Self::detect_tool();
+        let container = |kind| {
+            Some(ContainerInfo {
+                kind,
+                tool: tool.clone(),
+            })
+        };
+
+        // systemd and most runtimes export $container (also saved in /run/systemd/container)
+        let env_hint = std::env::var("container").ok().or_else(|| {
+            fs::read_to_string("/run/systemd/container")
+                .ok()
+                .map(|s| s.trim().to_string())
+        });
+        match env_hint.as_deref() {
+            Some("docker") => return container(ContainerKind::Docker),
+            Some("podman") | Some("oci") if Path::new("/run/.containerenv").exists() => {
+                return container(ContainerKind::Podman);
+            }
+            Some("lxc") | Some("lxc-libvirt") => return container(ContainerKind::Lxc),
+            Some("systemd-nspawn") => return container(ContainerKind::SystemdNspawn),
+            _ => {}
+        }
+
+        if Path::new("/run/.containerenv").exists() {
+            return container(ContainerKind::Podman);
+        }
+        if Path::new("/.dockerenv").exists() {
+            return container(ContainerKind::Docker);
+        }
+
+        // cgroup v1 paths name the runtime; with cgroup v2 namespaces this is just "/"
+        if let Ok(cgroup) = fs::read_to_string("/proc/1/cgroup") {
+            if cgroup.contains("kubepods") {
+                return container(ContainerKind::Kubernetes);
+            }
+            if cgroup.contains("docker") || cgroup.contains("containerd") {
+                return container(ContainerKind::Docker);
+            }
+            if cgroup.contains("libpod") {
+                return container(ContainerKind::Podman);
+            }
+            if cgroup.contains("/lxc") {
+                return container(ContainerKind::Lxc);
+            }
+        }
+
+        if fs::read_to_string("/proc/sys/kernel/osrelease")
+            .is_ok_and(|r| r.to_lowercase().contains("microsoft"))
+        {
+            return container(ContainerKind::Wsl);
+        }
+
+        env_hint.and_then(|_| container(ContainerKind::Other))
+    }
+
+    /// toolbox drops /run/.toolboxenv, distrobox exports its own variables
+    fn detect_tool() -> Option<String> {
+        if Path::new("/run/.toolboxenv").exists() {
+            Some("toolbox".to_string())
+        } else if std::env::var_os("DISTROBOX_ENTER_PATH").is_some()
+            || std::env::var_os("DISTROBOX_HOST_HOME").is_some()
+        {
+            Some("distrobox".to_string())
+        } else {
+            None
+        }
+    }
+}
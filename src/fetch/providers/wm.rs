@@ -0,0 +1,226 @@
+use super::version::cached_probe_version;
+use std::env;
+use std::fs;
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum SessionType {
+    Wayland,
+    X11,
+    Tty,
+}
+
+impl SessionType {
+    /// XDG_SESSION_TYPE when logind set it, otherwise whichever display is exported
+    pub fn detect() -> Self {
+        match env::var("XDG_SESSION_TYPE").as_deref() {
+            Ok("wayland") => Self::Wayland,
+            Ok("x11") => Self::X11,
+            _ if env::var_os("WAYLAND_DISPLAY").is_some() => Self::Wayland,
+            _ if env::var_os("DISPLAY").is_some() => Self::X11,
+            _ => Self::Tty,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Wayland => "Wayland",
+            Self::X11 => "X11",
+            Self::Tty => "TTY",
+        }
+    }
+}
+
+/// Which sessions a window manager can run in
+#[derive(Clone, Copy, PartialEq)]
+enum Protocol {
+    Wayland,
+    X11,
+    /// Runs as the Wayland compositor or the X11 WM, e.g. gnome-shell
+    Both,
+}
+
+/// Known window managers and compositors, in priority order: process name
+/// (as in /proc/<pid>/comm, cut at 15 bytes), display name, protocol and the
+/// flag that prints the version. Only exact names match, so "i3" doesn't
+/// catch "i3status" or "i3bar".
+const WMS: [(&str, &str, Protocol, Option<&str>); 40] = [
+    ("Hyprland", "Hyprland", Protocol::Wayland, Some("--version")),
+    ("sway", "Sway", Protocol::Wayland, Some("--version")),
+    ("niri", "niri", Protocol::Wayland, Some("--version")),
+    ("river", "river", Protocol::Wayland, Some("-version")),
+    ("kwin_wayland", "KWin", Protocol::Wayland, Some("--version")),
+    ("gnome-shell", "Mutter", Protocol::Both, Some("--version")),
+    ("cosmic-comp", "COSMIC", Protocol::Wayland, None),
+    ("labwc", "labwc", Protocol::Wayland, Some("--version")),
+    ("wayfire", "Wayfire", Protocol::Wayland, Some("--version")),
+    ("dwl", "dwl", Protocol::Wayland, Some("-v")),
+    ("hikari", "hikari", Protocol::Wayland, Some("-v")),
+    ("miracle-wm", "Miracle", Protocol::Wayland, None),
+    ("weston", "Weston", Protocol::Wayland, Some("--version")),
+    ("gamescope", "Gamescope", Protocol::Wayland, None),
+    ("cage", "Cage", Protocol::Wayland, None),
+    ("kwin_x11", "KWin", Protocol::X11, Some("--version")),
+    ("mutter", "Mutter", Protocol::Both, Some("--version")),
+    ("muffin", "Muffin", Protocol::X11, Some("--version")),
+    ("marco", "Marco", Protocol::X11, Some("--version")),
+    ("xfwm4", "Xfwm4", Protocol::X11, Some("--version")),
+    ("metacity", "Metacity", Protocol::X11, Some("--version")),
+    ("i3", "i3", Protocol::X11, Some("--version")),
+    ("bspwm", "bspwm", Protocol::X11, Some("-v")),
+    ("dwm", "dwm", Protocol::X11, None),
+    ("awesome", "awesome", Protocol::X11, Some("--version")),
+    ("qtile", "Qtile", Protocol::X11, None),
+    ("xmonad", "xmonad", Protocol::X11, None),
+    (
+        "herbstluftwm",
+        "herbstluftwm",
+        Protocol::X11,
+        Some("--version"),
+    ),
+    ("openbox", "Openbox", Protocol::X11, Some("--version")),
+    ("fluxbox", "Fluxbox", Protocol::X11, Some("-v")),
+    ("icewm", "IceWM", Protocol::X11, Some("--version")),
+    ("spectrwm", "spectrwm", Protocol::X11, None),
+    ("leftwm", "LeftWM", Protocol::X11, Some("--version")),
+    ("jwm", "JWM", Protocol::X11, Some("-v")),
+    ("fvwm3", "FVWM3", Protocol::X11, None),
+    ("fvwm", "FVWM", Protocol::X11, None),
+    ("enlightenment", "Enlightenment", Protocol::X11, None),
+    ("cwm", "cwm", Protocol::X11, None),
+    ("ratpoison", "Ratpoison", Protocol::X11, Some("-v")),
+    ("stumpwm", "StumpWM", Protocol::X11, None),
+];
+
+pub struct WmInfo {
+    pub name: String,
+    pub session: SessionType,
+    pub version: Option<String>,
+}
+
+impl WmInfo {
+    /// Compositor sockets first since they can't match the wrong process,
+    /// then an exact process-name scan, then EWMH on X11
+    pub fn detect(session: SessionType) -> Option<Self> {
+        let wm = |name: &str, version| Self {
+            name: name.to_string(),
+            session,
+            version,
+        };
+
+        if let Some(wm) = Self::from_sockets(session) {
+            return Some(wm);
+        }
+
+        let processes = Self::user_processes();
+        let found = WMS.iter().find_map(|&(proc_name, pretty, protocol, flag)| {
+            // An X11 WM can't manage a Wayland session (it'd be nested in Xwayland),
+            // and a Wayland compositor in an X11 session is nested (gamescope, cage)
+            match (session, protocol) {
+                (SessionType::Wayland, Protocol::X11) | (SessionType::X11, Protocol::Wayland) => {
+                    return None;
+                }
+                _ => {}
+            }
+            let (_, exe) = processes.iter().find(|(comm, _)| {
+                // xmonad's binary is xmonad-<arch>-<os>
+                comm == proc_name || (proc_name == "xmonad" && comm.starts_with("xmonad-"))
+            })?;
+            let version = flag.and_then(|flag| cached_probe_version(exe.to_str()?, &[flag]));
+            Some(wm(pretty, version))
+        });
+        if found.is_some() {
+            return found;
+        }
+
+        (session == SessionType::X11)
+            .then(Self::ewmh_name)
+            .flatten()
+            .map(|name| wm(&name, None))
+    }
+
+    /// "Hyprland 0.41.2 (Wayland)"
+    pub fn summary(&self) -> String {
+        match &self.version {
+            Some(version) => format!("{} {} ({})", self.name, version, self.session.label()),
+            None => format!("{} ({})", self.name, self.session.label()),
+        }
+    }
+
+    /// The variables are only trusted if the socket they point to exists:
+    /// a nested or restarted session can leave stale ones in the environment
+    fn from_sockets(session: SessionType) -> Option<Self> {
+        let runtime_dir = env::var_os("XDG_RUNTIME_DIR").map(PathBuf::from);
+
+        let hyprland = env::var("HYPRLAND_INSTANCE_SIGNATURE").ok().filter(|sig| {
+            // Moved from /tmp/hypr to $XDG_RUNTIME_DIR/hypr in 0.40
+            runtime_dir
+                .iter()
+                .map(|dir| dir.join("hypr").join(sig))
+                .chain([Path::new("/tmp/hypr").join(sig)])
+                .any(|dir| dir.exists())
+        });
+        let socket = |var: &str| env::var_os(var).filter(|path| Path::new(path).exists());
+
+        let (name, program, flag) = if hyprland.is_some() {
+            ("Hyprland", "Hyprland", "--version")
+        } else if socket("SWAYSOCK").is_some() {
+            ("Sway", "sway", "--version")
+        } else if socket("NIRI_SOCKET").is_some() {
+            ("niri", "niri", "--version")
+        } else {
+            return None;
+        };
+
+        Some(Self {
+            name: name.to_string(),
+            session,
+            version: cached_probe_version(program, &[flag]),
+        })
+    }
+
+    /// (comm, exe) of every process owned by the current user. Read straight
+    /// from /proc: the sysinfo process table isn't populated at startup.
+    fn user_processes() -> Vec<(String, PathBuf)> {
+        // SAFETY: getuid takes no arguments, cannot fail and has no side effects
+        let uid = unsafe { libc::getuid() };
+        let Ok(entries) = fs::read_dir("/proc") else {
+            return Vec::new();
+        };
+
+        entries
+            .flatten()
+            .filter(|entry| {
+                entry.file_name().to_string_lossy().parse::<u32>().is_ok()
+                    && entry.metadata().is_ok_and(|m| m.uid() == uid)
+            })
+            .filter_map(|entry| {
+                let comm = fs::read_to_string(entry.path().join("comm")).ok()?;
+                let exe = fs::read_link(entry.path().join("exe")).unwrap_or_default();
+                Some((comm.trim().to_string(), exe))
+            })
+            .collect()
+    }
+
+    /// _NET_WM_NAME of the window the root's _NET_SUPPORTING_WM_CHECK points
+    /// to, the EWMH way of naming the running WM
+    fn ewmh_name() -> Option<String> {
+        let xprop = |args: &[&str]| {
+            let output = Command::new("xprop").args(args).output().ok()?;
+            output
+                .status
+                .success()
+                .then(|| String::from_utf8_lossy(&output.stdout).to_string())
+        };
+
+        // "_NET_SUPPORTING_WM_CHECK(WINDOW): window id # 0x1a00003"
+        let check = xprop(&["-root", "_NET_SUPPORTING_WM_CHECK"])?;
+        let id = check.split_whitespace().last()?;
+        // "_NET_WM_NAME(UTF8_STRING) = \"Openbox\""
+        let name = xprop(&["-id", id, "_NET_WM_NAME"])?;
+        let name = name.split_once(" = ")?.1.trim().trim_matches('"');
+        (!name.is_empty()).then(|| name.to_string())
+    }
+}
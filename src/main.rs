@@ -1,6 +1,6 @@
 use clap::Parser;
 use crossterm::event::{self, Event, KeyCode, MouseEventKind};
-use mizu::app::{App, AppTab};
+use mizu::app::{App, AppTab, NetworkView};
 use mizu::tui::{init, restore};
 use mizu::ui::render;
 use std::path::PathBuf;
@@ -20,10 +20,14 @@ struct Args {
 
     #[arg(short, long)]
     image: Option<PathBuf>,
+
+    /// Show the logo of this distro ID (e.g. "arch", "ubuntu") instead of the detected one
+    #[arg(long)]
+    distro: Option<String>,
 }
 
 fn main() -> std::io::Result<()> {
-    let _args = Args::parse();
+    let args = Args::parse();
 
     // Initialize terminal
     // We use ratatui with crossterm backend for rendering.
@@ -33,6 +37,7 @@ fn main() -> std::io::Result<()> {
     // Create app state
     // 'App::new()' loads configuration and initializes system info handles.
     let mut app = App::new();
+    app.distro_override = args.distro;
 
     let mut last_tick = Instant::now();
 
@@ -58,6 +63,8 @@ fn main() -> std::io::Result<()> {
                         KeyCode::Esc => {
                             if app.show_help {
                                 app.show_help = false;
+                            } else {
+                                app.close_explorer();
                             }
                         }
                         KeyCode::Char('s') => {
@@ -65,6 +72,33 @@ fn main() -> std::io::Result<()> {
                                 app.process_sort = app.process_sort.next();
                             }
                         }
+                        KeyCode::Char('v') if !app.show_help => match app.current_tab {
+                            AppTab::Network => app.toggle_network_view(),
+                            AppTab::Disks => app.toggle_disk_view(),
+                            _ => {}
+                        },
+                        // Not Ctrl+C
+                        KeyCode::Char('c') if key.modifiers.is_empty() => {
+                            if !app.show_help && matches!(app.current_tab, AppTab::Dashboard) {
+                                app.toggle_per_core();
+                            }
+                        }
+                        KeyCode::Char('f') => {
+                            if !app.show_help
+                                && matches!(app.current_tab, AppTab::Network)
+                                && app.network_view == NetworkView::Connections
+                            {
+                                app.cycle_connection_filter();
+                            }
+                        }
+                        KeyCode::Char('l') => {
+                            if !app.show_help
+                                && matches!(app.current_tab, AppTab::Network)
+                                && app.network_view == NetworkView::Connections
+                            {
+                                app.toggle_listening_only();
+                            }
+                        }
                         _ => {
                             // Only process other keys if help is NOT shown
                             if !app.show_help {
@@ -74,20 +108,33 @@ fn main() -> std::io::Result<()> {
                                     KeyCode::Char('1') => app.current_tab = AppTab::Dashboard,
                                     KeyCode::Char('2') => app.current_tab = AppTab::Processes,
                                     KeyCode::Char('3') => app.current_tab = AppTab::Network,
-                                    KeyCode::Char('4') => app.current_tab = AppTab::Settings,
+                                    KeyCode::Char('4') => app.current_tab = AppTab::Disks,
+                                    KeyCode::Char('5') => app.current_tab = AppTab::Packages,
+                                    KeyCode::Char('6') => app.current_tab = AppTab::Settings,
                                     KeyCode::Down | KeyCode::Char('j') => match app.current_tab {
                                         AppTab::Processes => app.scroll_down(),
+                                        AppTab::Network => app.network_next(),
+                                        AppTab::Disks => app.disk_next(),
+                                        AppTab::Packages => app.package_scroll_down(),
                                         AppTab::Settings => app.settings_next(),
                                         _ => {}
                                     },
                                     KeyCode::Up | KeyCode::Char('k') => match app.current_tab {
                                         AppTab::Processes => app.scroll_up(),
+                                        AppTab::Network => app.network_previous(),
+                                        AppTab::Disks => app.disk_previous(),
+                                        AppTab::Packages => app.package_scroll_up(),
                                         AppTab::Settings => app.settings_previous(),
                                         _ => {}
                                     },
-                                    KeyCode::Enter => {
-                                        if let AppTab::Settings = app.current_tab {
-                                            app.settings_toggle();
+                                    KeyCode::Enter => match app.current_tab {
+                                        AppTab::Settings => app.settings_toggle(),
+                                        AppTab::Disks => app.disk_enter(),
+                                        _ => {}
+                                    },
+                                    KeyCode::Backspace | KeyCode::Char('h') => {
+                                        if let AppTab::Disks = app.current_tab {
+                                            app.disk_back();
                                         }
                                     }
                                     _ => {}
@@ -97,22 +144,24 @@ fn main() -> std::io::Result<()> {
                     }
                 }
                 // Mouse Input
-                Event::Mouse(mouse_event) => {
-                    if !app.show_help {
-                        match mouse_event.kind {
-                            MouseEventKind::ScrollDown => {
-                                if let AppTab::Processes = app.current_tab {
-                                    app.scroll_down();
-                                }
-                            }
-                            MouseEventKind::ScrollUp => {
-                                if let AppTab::Processes = app.current_tab {
-                                    app.scroll_up();
-                                }
-                            }
-                            // TODO: Handle tab clicking if we track area rects
+                Event::Mouse(mouse_event) if !app.show_help => {
+                    match mouse_event.kind {
+                        MouseEventKind::ScrollDown => match app.current_tab {
+                            AppTab::Processes => app.scroll_down(),
+                            AppTab::Network => app.network_next(),
+                            AppTab::Disks => app.disk_next(),
+                            AppTab::Packages => app.package_scroll_down(),
                             _ => {}
-                        }
+                        },
+                        MouseEventKind::ScrollUp => match app.current_tab {
+                            AppTab::Processes => app.scroll_up(),
+                            AppTab::Network => app.network_previous(),
+                            AppTab::Disks => app.disk_previous(),
+                            AppTab::Packages => app.package_scroll_up(),
+                            _ => {}
+                        },
+                        // TODO: Handle tab clicking if we track area rects
+                        _ => {}
                     }
                 }
                 _ => {}
@@ -1,10 +1,18 @@
-use crate::app::{App, AppTab, ProcessSortMode};
+use crate::app::{App, AppTab, DirExplorer, DisksView, NetworkView, ProcessSortMode};
 use crate::config::parse_color;
 use crate::draw::logos::get_logo;
+use crate::fetch::providers::block_devices::{BlockDevice, BlockDeviceKind};
+use crate::fetch::providers::connections::SocketState;
+use crate::fetch::providers::memory::format_size;
+use crate::fetch::providers::network::{NET_HISTORY_LEN, NetworkInfo};
+use crate::fetch::providers::package_history::TransactionAction;
+use crate::fetch::providers::pressure::{Level, Psi};
 use crate::ui::widgets::CyberpunkBlock;
 use ratatui::{
     prelude::*,
-    widgets::{Block, Borders, Gauge, Paragraph, Row, Table, Tabs, Wrap},
+    widgets::{
+        Axis, Block, Borders, Chart, Dataset, Gauge, GraphType, Paragraph, Row, Table, Tabs, Wrap,
+    },
 };
 
 pub mod widgets;
@@ -38,6 +46,8 @@ pub fn render(app: &App, frame: &mut Frame) {
         AppTab::Dashboard => render_dashboard(app, frame, content_area),
         AppTab::Processes => render_processes(app, frame, content_area),
         AppTab::Network => render_network(app, frame, content_area),
+        AppTab::Disks => render_disks(app, frame, content_area),
+        AppTab::Packages => render_packages(app, frame, content_area),
         AppTab::Settings => render_settings(app, frame, content_area),
     }
 
@@ -58,10 +68,14 @@ fn render_help_popup(app: &App, frame: &mut Frame, area: Rect) {
         "Keyboard Shortcuts",
         "------------------",
         "Tab / Shift+Tab : Navigate Tabs",
-        "1, 2, 3, 4      : Jump to Tab",
+        "1 - 6           : Jump to Tab",
         "j / Down        : Scroll Down / Next Option",
         "k / Up          : Scroll Up / Prev Option",
         "Enter           : Toggle Setting",
+        "c               : Per-core CPU View (Dashboard)",
+        "v               : Switch Network / Disks View",
+        "f / l           : Filter Sockets / Listening",
+        "Enter/Backspace : Explore Mount / Go Up",
         "?               : Toggle Help",
         "q               : Quit",
         "",
@@ -237,7 +251,7 @@ fn render_top_screen(app: &App, frame: &mut Frame, area: Rect, is_width_compact:
                 .direction(Direction::Vertical)
                 .constraints([Constraint::Min(8), Constraint::Min(8)])
                 .split(area);
-            render_logo(app, frame, chunks[0]);
+            render_logo(app, frame, chunks[0], true);
             render_info(app, frame, chunks[1]);
         } else {
             // Không đủ cao: Chỉ hiện Info (Quan trọng hơn)
@@ -252,36 +266,41 @@ fn render_top_screen(app: &App, frame: &mut Frame, area: Rect, is_width_compact:
                 Constraint::Percentage(60), // Info
             ])
             .split(area);
-        render_logo(app, frame, chunks[0]);
+        render_logo(app, frame, chunks[0], false);
         render_info(app, frame, chunks[1]);
     }
 }
 
-fn render_logo(app: &App, frame: &mut Frame, area: Rect) {
-    let logo_lines = get_logo(&app.system_info.os.name);
-    let logo_text = logo_lines.join("\n");
+fn render_logo(app: &App, frame: &mut Frame, area: Rect, compact: bool) {
+    let logo = match &app.distro_override {
+        Some(distro) => get_logo(&[distro.as_str()]),
+        None => get_logo(&app.system_info.os.release.logo_ids()),
+    };
 
     // Tự động ẩn border nếu area quá nhỏ
-    if area.width > 20 && area.height > 5 {
+    let bordered = area.width > 20 && area.height > 5;
+    let inner_area = if bordered {
+        Rect::new(area.x + 1, area.y + 1, area.width - 2, area.height - 2)
+    } else {
+        area
+    };
+
+    // Fall back to the small variant whenever the large one would be clipped
+    let compact = compact
+        || logo.width(false) > inner_area.width as usize
+        || logo.height(false) > inner_area.height as usize;
+
+    if bordered {
         let block = CyberpunkBlock::new(" System ", parse_color(&app.config.theme.border_color));
         // We need to render the block separately because Paragraph doesn't accept a Widget as block,
         // it accepts a Block struct.
         // Since CyberpunkBlock is a Widget that wraps a Block, we'll render it as a background/border
         // then render the paragraph inside.
         frame.render_widget(block, area);
+    }
 
-        let inner_area = Rect::new(area.x + 1, area.y + 1, area.width - 2, area.height - 2);
-
-        let logo_paragraph = Paragraph::new(logo_text)
-            .style(Style::default().fg(parse_color(&app.config.theme.key_color)))
-            .alignment(Alignment::Center);
-        frame.render_widget(logo_paragraph, inner_area);
-    } else {
-        let logo_paragraph = Paragraph::new(logo_text)
-            .style(Style::default().fg(parse_color(&app.config.theme.key_color)))
-            .alignment(Alignment::Center);
-        frame.render_widget(logo_paragraph, area);
-    };
+    let logo_paragraph = Paragraph::new(logo.lines(compact)).alignment(Alignment::Center);
+    frame.render_widget(logo_paragraph, inner_area);
 }
 
 fn render_info(app: &App, frame: &mut Frame, area: Rect) {
@@ -302,17 +321,47 @@ fn render_info(app: &App, frame: &mut Frame, area: Rect) {
     for module in &app.config.modules {
         match module.as_str() {
             "os" => info_lines.push(format!(" OS:             {}", app.system_info.os.name)),
-            "host" => info_lines.push(format!(" Host:           {}", app.system_info.os.hostname)),
+            "hostname" => {
+                info_lines.push(format!(" Hostname:       {}", app.system_info.os.hostname))
+            }
+            "host" => info_lines.push(format!(
+                " Host:           {}",
+                app.system_info.host.get_formatted_model()
+            )),
+            "virt" => info_lines.push(format!(
+                " Virt:           {}",
+                app.system_info.virt.get_formatted()
+            )),
+            "bios" => info_lines.push(format!(
+                " BIOS:           {}",
+                app.system_info.host.get_formatted_bios()
+            )),
             "kernel" => info_lines.push(format!(" Kernel:         {}", app.system_info.os.kernel)),
             "uptime" => info_lines.push(format!(
                 " Uptime:         {}",
                 app.system_info.get_formatted_uptime()
             )),
-            "packages" => {
-                info_lines.push(format!(" Packages:       {}", app.system_info.packages))
-            }
+            "packages" => info_lines.push(format!(
+                " Packages:       {}",
+                app.system_info.get_formatted_packages()
+            )),
+            "last_upgrade" => info_lines.push(format!(
+                " Last Upgrade:   {}",
+                app.system_info.get_formatted_last_upgrade()
+            )),
+            "recent_packages" => info_lines.push(format!(
+                " Recent Pkgs:    {}",
+                app.system_info.get_formatted_recent_packages()
+            )),
             "shell" => info_lines.push(format!(" Shell:          {}", app.system_info.os.shell)),
-            "display" => info_lines.push(format!(" Display:        {}", app.system_info.display)),
+            "display" => {
+                if app.system_info.displays.is_empty() {
+                    info_lines.push(" Display:        Unknown".to_string());
+                }
+                for monitor in &app.system_info.displays {
+                    info_lines.push(format!(" Display:        {}", monitor.summary()));
+                }
+            }
             "de" => info_lines.push(format!(" DE:             {}", app.system_info.os.de_wm)),
             "wm" => info_lines.push(format!(" WM:             {}", app.system_info.os.wm)),
             "wm_theme" => {
@@ -325,13 +374,33 @@ fn render_info(app: &App, frame: &mut Frame, area: Rect) {
             "terminal" => {
                 info_lines.push(format!(" Terminal:       {}", app.system_info.os.terminal))
             }
+            "terminal_font" => info_lines.push(format!(
+                " Terminal Font:  {}",
+                app.system_info.terminal_font
+            )),
+            "terminal_theme" => info_lines.push(format!(
+                " Terminal Theme: {}",
+                app.system_info.terminal_theme
+            )),
+            "multiplexer" => info_lines.push(format!(
+                " Multiplexer:    {}",
+                app.system_info.os.multiplexer
+            )),
             "cpu" => info_lines.push(format!(
                 " CPU:            {}",
-                app.system_info
-                    .cpu_info
-                    .models
-                    .first()
-                    .unwrap_or(&"Unknown".to_string())
+                app.system_info.cpu_info.get_formatted()
+            )),
+            "cpu_topology" => info_lines.push(format!(
+                " CPU Topology:   {}",
+                app.system_info.cpu_info.get_formatted_topology()
+            )),
+            "cpu_freq" => info_lines.push(format!(
+                " CPU Frequency:  {}",
+                app.system_info.cpu_info.get_formatted_frequency()
+            )),
+            "cpu_cache" => info_lines.push(format!(
+                " CPU Cache:      {}",
+                app.system_info.cpu_info.get_formatted_caches()
             )),
             "gpu" => info_lines.push(format!(
                 "﬙ GPU:            {}",
@@ -343,7 +412,41 @@ fn render_info(app: &App, frame: &mut Frame, area: Rect) {
                 app.system_info.memory_total as f64 / 1024.0 / 1024.0 / 1024.0,
                 (app.system_info.memory_used as f64 / app.system_info.memory_total as f64) * 100.0
             )),
-            "disk" => info_lines.push(format!(" Disk:           {}", app.system_info.disk_usage)),
+            "memory_breakdown" => info_lines.push(format!(
+                " Memory Detail:  {}",
+                app.system_info.memory.get_formatted_breakdown()
+            )),
+            "huge_pages" => info_lines.push(format!(
+                " Huge Pages:     {}",
+                app.system_info.memory.get_formatted_huge_pages()
+            )),
+            "swap" => info_lines.push(format!(
+                " Swap:           {}",
+                app.system_info.memory.get_formatted_swaps()
+            )),
+            "zswap" => info_lines.push(format!(
+                " Zswap:          {}",
+                app.system_info.memory.get_formatted_zswap()
+            )),
+            "load" => info_lines.push(format!(
+                " Load:           {}",
+                app.system_info
+                    .pressure
+                    .get_formatted_load(app.system_info.cpu_info.cores)
+            )),
+            "pressure" => info_lines.push(format!(
+                " Pressure:       {}",
+                app.system_info.pressure.get_formatted_pressure()
+            )),
+            "disk" => {
+                if app.system_info.disks.is_empty() {
+                    info_lines.push(" Disk:           Unknown".to_string());
+                }
+                for disk in &app.system_info.disks {
+                    let label = format!("Disk ({}):", disk.mount_point);
+                    info_lines.push(format!(" {:<16}{}", label, disk.summary()));
+                }
+            }
             "battery" => info_lines.push(format!(" Battery:        {}", app.system_info.battery)),
             "locale" => info_lines.push(format!(" Locale:         {}", app.system_info.os.locale)),
             "local_ip" => {
@@ -405,38 +508,55 @@ fn render_bottom_screen(app: &App, frame: &mut Frame, area: Rect, is_compact: bo
         area
     };
 
-    let constraints = if is_compact {
-        vec![
-            Constraint::Length(1), // CPU (1 dòng)
-            Constraint::Length(1), // RAM (1 dòng)
-            Constraint::Length(1), // Swap (1 dòng)
-            Constraint::Min(1),    // Disk + GPU
-        ]
-    } else {
-        vec![
-            Constraint::Length(3), // CPU (Gauge to)
-            Constraint::Length(3), // RAM (Gauge to)
-            Constraint::Length(3), // Swap (Gauge to)
-            Constraint::Min(1),    // Disk + GPU
-        ]
-    };
+    let gauge_height = if is_compact { 1 } else { 3 };
+    let margin = if is_compact { 0 } else { 1 };
+    let show_disk_io = app.config.show_disk_io_gauge && app.system_info.disk_io.busiest().is_some();
+
+    // The per-core view takes what the other gauges and the info line leave over
+    let per_core = (app.show_per_core && !app.system_info.core_usage.is_empty()).then(|| {
+        // The block title, or in compact mode a first row with the aggregate
+        let title_rows = 1;
+        let other_rows = gauge_height * (2 + show_disk_io as u16) + 2 + margin * 2;
+        let max_rows = inner_area
+            .height
+            .saturating_sub(other_rows + title_rows)
+            .max(1);
+        let width = inner_area.width.saturating_sub(margin * 2);
+        let (layout, rows) = core_layout(app.system_info.core_usage.len(), width, max_rows);
+        (layout, rows + title_rows)
+    });
+
+    let mut constraints = vec![
+        Constraint::Length(per_core.map_or(gauge_height, |(_, height)| height)), // CPU
+        Constraint::Length(gauge_height),                                        // RAM
+        Constraint::Length(gauge_height),                                        // Swap
+    ];
+    if show_disk_io {
+        constraints.push(Constraint::Length(gauge_height)); // Disk I/O
+    }
+    constraints.push(Constraint::Length(1)); // Load / PSI strip
+    constraints.push(Constraint::Min(1)); // Disk + GPU
 
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints(constraints)
-        .margin(if is_compact { 0 } else { 1 }) // Bỏ margin trong chế độ compact
+        .margin(margin) // Bỏ margin trong chế độ compact
         .split(inner_area);
 
     // --- CPU GAUGE ---
-    let cpu_label = format!(
-        "CPU: {:.1}% ({})",
-        app.system_info.cpu_usage,
-        app.system_info
-            .cpu_info
-            .models
-            .first()
-            .unwrap_or(&"Unknown".to_string())
-    );
+    let cgroup = &app.system_info.cgroup;
+    let cpu_label = match cgroup.cpu_limit.filter(|_| cgroup.cpu_limited()) {
+        // Usage is measured against the quota, say so
+        Some(cpus) => format!(
+            "CPU: {:.1}% of {:.1} CPUs [cgroup limit]",
+            app.system_info.cpu_usage, cpus
+        ),
+        None => format!(
+            "CPU: {:.1}% ({})",
+            app.system_info.cpu_usage,
+            app.system_info.cpu_info.name()
+        ),
+    };
     let cpu_ratio = app.system_info.cpu_usage as f64 / 100.0;
     let cpu_color = if app.system_info.cpu_usage > 80.0 {
         parse_color(&app.config.theme.gauge_cpu_high)
@@ -454,20 +574,30 @@ fn render_bottom_screen(app: &App, frame: &mut Frame, area: Rect, is_compact: bo
     } else {
         // Block Gauge đầy đủ cho Normal mode
         Gauge::default()
-            .block(Block::default().title("CPU Load"))
+            .block(Block::default().title(if cgroup.cpu_limited() {
+                "CPU Load (limited)"
+            } else {
+                "CPU Load"
+            }))
             .gauge_style(Style::default().fg(cpu_color))
             .ratio(cpu_ratio)
             .label(cpu_label)
     };
-    frame.render_widget(cpu_gauge, chunks[0]);
+    match per_core {
+        Some((layout, _)) => render_per_core(app, frame, chunks[0], layout, is_compact),
+        None => frame.render_widget(cpu_gauge, chunks[0]),
+    }
 
     // --- RAM GAUGE ---
     let ram_ratio = app.system_info.memory_used as f64 / app.system_info.memory_total as f64;
-    let ram_label = format!(
+    let mut ram_label = format!(
         "RAM: {}/{} MB",
         app.system_info.memory_used / 1024 / 1024,
         app.system_info.memory_total / 1024 / 1024
     );
+    if cgroup.memory_limited() {
+        ram_label.push_str(" [cgroup limit]");
+    }
 
     let ram_gauge = if is_compact {
         Gauge::default()
@@ -477,12 +607,21 @@ fn render_bottom_screen(app: &App, frame: &mut Frame, area: Rect, is_compact: bo
             .use_unicode(true)
     } else {
         Gauge::default()
-            .block(Block::default().title("Memory Usage"))
+            .block(Block::default().title(if cgroup.memory_limited() {
+                "Memory Usage (limited)"
+            } else {
+                "Memory Usage"
+            }))
             .gauge_style(Style::default().fg(parse_color(&app.config.theme.gauge_ram)))
             .ratio(ram_ratio)
             .label(ram_label)
     };
-    frame.render_widget(ram_gauge, chunks[1]);
+    // Host-wide breakdown; inside a memory-limited cgroup the gauge is the meaningful one
+    if !is_compact && !cgroup.memory_limited() && app.system_info.memory.breakdown.total > 0 {
+        render_memory_bar(app, frame, chunks[1]);
+    } else {
+        frame.render_widget(ram_gauge, chunks[1]);
+    }
 
     // --- SWAP GAUGE ---
     let swap_percent = if app.system_info.swap_total > 0 {
@@ -510,23 +649,54 @@ fn render_bottom_screen(app: &App, frame: &mut Frame, area: Rect, is_compact: bo
     };
     frame.render_widget(swap_gauge, chunks[2]);
 
+    // --- DISK I/O GAUGE (optional) ---
+    if show_disk_io && let Some(dev) = app.system_info.disk_io.busiest() {
+        let io_label = format!(
+            "{}: {:.0}% busy | R {} W {}",
+            dev.device,
+            dev.busy_percent,
+            format_rate(dev.read_bytes_per_sec),
+            format_rate(dev.write_bytes_per_sec)
+        );
+        let io_gauge = Gauge::default()
+            .gauge_style(Style::default().fg(parse_color(&app.config.theme.gauge_cpu_low)))
+            .ratio((dev.busy_percent / 100.0).clamp(0.0, 1.0))
+            .label(io_label);
+        let io_gauge = if is_compact {
+            io_gauge.use_unicode(true)
+        } else {
+            io_gauge.block(Block::default().title("Disk I/O"))
+        };
+        frame.render_widget(io_gauge, chunks[3]);
+    }
+
+    // --- LOAD / PSI STRIP ---
+    let strip_index = chunks.len() - 2;
+    frame.render_widget(Paragraph::new(pressure_strip(app)), chunks[strip_index]);
+
     // --- DISK & GPU INFO ---
     let gpu_text = app.system_info.gpus.join(", ");
     let other_info = format!(
         " Disk (/): {}\n GPU(s):   {}",
-        app.system_info.disk_usage, gpu_text
+        app.system_info.get_formatted_root_disk(),
+        gpu_text
     );
     let other_paragraph = Paragraph::new(other_info)
         .style(Style::default().fg(parse_color(&app.config.theme.value_color)))
         .wrap(Wrap { trim: true });
 
-    if chunks.len() > 3 {
-        frame.render_widget(other_paragraph, chunks[3]);
+    if let Some(last) = chunks.last() {
+        frame.render_widget(other_paragraph, *last);
     }
 }
 
 // --- NETWORK MONITOR RENDER ---
 fn render_network(app: &App, frame: &mut Frame, area: Rect) {
+    if app.network_view == NetworkView::Connections {
+        render_connections(app, frame, area);
+        return;
+    }
+
     let block = CyberpunkBlock::new(
         " Network Monitor ",
         parse_color(&app.config.theme.border_color),
@@ -535,16 +705,24 @@ fn render_network(app: &App, frame: &mut Frame, area: Rect) {
 
     let inner_area = Rect::new(area.x + 1, area.y + 1, area.width - 2, area.height - 2);
 
-    // Split inner area for interface list and details
+    // Split inner area for interface list, details, history chart and status
+    let table_height = (app.system_info.networks.len() as u16 + 2).max(5);
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Min(5), Constraint::Length(3)])
+        .constraints([
+            Constraint::Length(table_height),
+            Constraint::Length(4),
+            Constraint::Min(6),
+            Constraint::Length(2),
+        ])
         .split(inner_area);
 
     // Interface List
     let header = Row::new(vec![
         "Interface",
-        "IP Address",
+        "Kind",
+        "State",
+        "Address",
         "RX Speed",
         "TX Speed",
         "Total RX",
@@ -561,24 +739,28 @@ fn render_network(app: &App, frame: &mut Frame, area: Rect) {
         .system_info
         .networks
         .iter()
-        .map(|network_info| {
-            // Calculate speed in KB/s
-            // refresh_rate_ms is the interval, e.g., 250ms
-            // rx is bytes received in that interval
-            // Speed = (bytes / (interval_ms / 1000.0)) / 1024.0
-            let seconds = app.refresh_rate_ms as f64 / 1000.0;
-            let rx_speed = (network_info.rx as f64 / seconds) / 1024.0;
-            let tx_speed = (network_info.tx as f64 / seconds) / 1024.0;
+        .enumerate()
+        .map(|(i, network_info)| {
+            // Rates come from timestamped samples in NetworkProvider
+            let style = if i == app.network_selected {
+                Style::default().fg(Color::Black).bg(Color::Cyan)
+            } else if network_info.operstate == "down" {
+                Style::default().fg(Color::DarkGray)
+            } else {
+                Style::default().fg(parse_color(&app.config.theme.text_color))
+            };
 
             Row::new(vec![
                 network_info.name.clone(),
-                network_info.ip_v4.clone(),
-                format!("{:.1} KB/s", rx_speed),
-                format!("{:.1} KB/s", tx_speed),
+                network_info.kind.label().to_string(),
+                network_info.operstate.clone(),
+                network_info.primary_address().to_string(),
+                format_rate(network_info.rx_rate),
+                format_rate(network_info.tx_rate),
                 format!("{:.1} MB", network_info.total_rx as f64 / 1024.0 / 1024.0),
                 format!("{:.1} MB", network_info.total_tx as f64 / 1024.0 / 1024.0),
             ])
-            .style(Style::default().fg(parse_color(&app.config.theme.text_color)))
+            .style(style)
         })
         .collect();
 
@@ -586,7 +768,9 @@ fn render_network(app: &App, frame: &mut Frame, area: Rect) {
         rows,
         [
             Constraint::Length(15), // Interface
-            Constraint::Length(16), // IP Address
+            Constraint::Length(9),  // Kind
+            Constraint::Length(8),  // State
+            Constraint::Length(20), // Address
             Constraint::Length(12), // RX Speed
             Constraint::Length(12), // TX Speed
             Constraint::Length(12), // Total RX
@@ -598,12 +782,1040 @@ fn render_network(app: &App, frame: &mut Frame, area: Rect) {
 
     frame.render_widget(table, chunks[0]);
 
-    // Summary / Status
-    let status_text = format!("Total Interfaces: {}", app.system_info.networks.len());
-    let p = Paragraph::new(status_text)
+    if let Some(network_info) = app.system_info.networks.get(app.network_selected) {
+        render_network_details(app, network_info, frame, chunks[1]);
+        // RX/TX history of the selected interface
+        render_network_chart(app, network_info, frame, chunks[2]);
+    }
+
+    // Routing / DNS summary
+    let routing = &app.system_info.routing;
+    let uplink = routing.uplink().unwrap_or("none");
+    let mut dns_text = format!("DNS: {}", routing.dns.summary());
+    if !routing.dns.search.is_empty() {
+        dns_text.push_str(&format!("  |  Search: {}", routing.dns.search.join(" ")));
+    }
+    let status_lines = vec![
+        Line::from(format!(
+            "Gateway: {}  |  Uplink: {}  |  Total Interfaces: {}",
+            routing.get_formatted_gateway(),
+            uplink,
+            app.system_info.networks.len()
+        )),
+        Line::from(dns_text),
+    ];
+    let p = Paragraph::new(status_lines)
         .style(Style::default().fg(parse_color(&app.config.theme.value_color)))
         .alignment(Alignment::Center);
-    frame.render_widget(p, chunks[1]);
+    frame.render_widget(p, chunks[3]);
+}
+
+fn render_connections(app: &App, frame: &mut Frame, area: Rect) {
+    let mut title = " Connections ".to_string();
+    if let Some(state) = app.connection_filter {
+        title.push_str(&format!("[{}] ", state.label()));
+    }
+    if app.listening_only {
+        title.push_str("[listening] ");
+    }
+    let block = CyberpunkBlock::new(&title, parse_color(&app.config.theme.border_color));
+    frame.render_widget(block, area);
+
+    let inner_area = Rect::new(area.x + 1, area.y + 1, area.width - 2, area.height - 2);
+    let connections = app.visible_connections();
+
+    if connections.is_empty() {
+        let p = Paragraph::new("No matching sockets")
+            .style(Style::default().fg(parse_color(&app.config.theme.value_color)))
+            .alignment(Alignment::Center);
+        frame.render_widget(p, inner_area);
+        return;
+    }
+
+    let header = Row::new(vec![
+        "Proto",
+        "State",
+        "Local Address",
+        "Peer Address",
+        "Process",
+    ])
+    .style(
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+    )
+    .bottom_margin(1);
+
+    let rows: Vec<Row> = connections
+        .iter()
+        .skip(
+            app.connection_scroll
+                .min(connections.len().saturating_sub(1)),
+        )
+        .map(|conn| {
+            let color = match conn.state {
+                SocketState::Listen => parse_color(&app.config.theme.key_color),
+                SocketState::Established => parse_color(&app.config.theme.value_color),
+                SocketState::TimeWait | SocketState::CloseWait => Color::DarkGray,
+                _ => parse_color(&app.config.theme.text_color),
+            };
+            let process = match (&conn.process, conn.pid) {
+                (Some(name), Some(pid)) => format!("{} ({})", name, pid),
+                (None, Some(pid)) => pid.to_string(),
+                _ => "-".to_string(),
+            };
+
+            Row::new(vec![
+                conn.protocol.label().to_string(),
+                conn.state.label().to_string(),
+                conn.local.clone(),
+                conn.remote.clone(),
+                process,
+            ])
+            .style(Style::default().fg(color))
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(5),  // Proto
+            Constraint::Length(10), // State
+            Constraint::Min(24),    // Local Address
+            Constraint::Min(24),    // Peer Address
+            Constraint::Min(16),    // Process
+        ],
+    )
+    .header(header)
+    .column_spacing(2);
+
+    frame.render_widget(table, inner_area);
+}
+
+fn render_network_details(app: &App, network_info: &NetworkInfo, frame: &mut Frame, area: Rect) {
+    let key_style = Style::default().fg(parse_color(&app.config.theme.key_color));
+    let value_style = Style::default().fg(parse_color(&app.config.theme.value_color));
+    let field = |key: &'static str, value: String| {
+        vec![
+            Span::styled(key, key_style),
+            Span::styled(value, value_style),
+            Span::raw("   "),
+        ]
+    };
+
+    let mut link = field(
+        "MAC: ",
+        network_info.mac.clone().unwrap_or_else(|| "-".to_string()),
+    );
+    link.extend(field(
+        "MTU: ",
+        network_info.mtu.map_or("-".to_string(), |m| m.to_string()),
+    ));
+    link.extend(field(
+        "Speed: ",
+        network_info
+            .speed_mbps
+            .map_or("-".to_string(), |s| format!("{} Mb/s", s)),
+    ));
+    if let Some(wireless) = &network_info.wireless {
+        link.extend(field(
+            "SSID: ",
+            wireless.ssid.clone().unwrap_or_else(|| "-".to_string()),
+        ));
+        let signal = match (wireless.quality, wireless.signal_dbm) {
+            (Some(q), Some(dbm)) => format!("{}% ({} dBm)", q, dbm),
+            (Some(q), None) => format!("{}%", q),
+            (None, Some(dbm)) => format!("{} dBm", dbm),
+            (None, None) => "-".to_string(),
+        };
+        link.extend(field("Signal: ", signal));
+    }
+
+    let addresses = if network_info.addresses.is_empty() {
+        "-".to_string()
+    } else {
+        network_info.addresses.join(", ")
+    };
+
+    let lines = vec![
+        Line::from(link),
+        Line::from(field("Addresses: ", addresses)),
+    ];
+    let p = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(format!(" {} ", network_info.name))
+                .borders(Borders::TOP)
+                .border_style(Style::default().fg(parse_color(&app.config.theme.border_color))),
+        )
+        .wrap(Wrap { trim: true });
+    frame.render_widget(p, area);
+}
+
+fn render_network_chart(app: &App, network_info: &NetworkInfo, frame: &mut Frame, area: Rect) {
+    let to_points = |history: &std::collections::VecDeque<f64>| -> Vec<(f64, f64)> {
+        // Right-align so the newest sample is always at the right edge
+        let offset = NET_HISTORY_LEN - history.len();
+        history
+            .iter()
+            .enumerate()
+            .map(|(i, &v)| ((offset + i) as f64, v))
+            .collect()
+    };
+    let rx_points = to_points(&network_info.rx_history);
+    let tx_points = to_points(&network_info.tx_history);
+
+    let max = rx_points
+        .iter()
+        .chain(tx_points.iter())
+        .map(|&(_, v)| v)
+        .fold(1024.0, f64::max);
+
+    let datasets = vec![
+        Dataset::default()
+            .name("RX")
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(parse_color(&app.config.theme.gauge_cpu_low)))
+            .data(&rx_points),
+        Dataset::default()
+            .name("TX")
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(parse_color(&app.config.theme.gauge_ram)))
+            .data(&tx_points),
+    ];
+
+    let chart = Chart::new(datasets)
+        .block(
+            Block::default()
+                .title(format!(" {} history ", network_info.name))
+                .borders(Borders::TOP)
+                .border_style(Style::default().fg(parse_color(&app.config.theme.border_color))),
+        )
+        .x_axis(Axis::default().bounds([0.0, NET_HISTORY_LEN as f64]))
+        .y_axis(
+            Axis::default()
+                .bounds([0.0, max])
+                .labels(vec![Span::raw("0"), Span::raw(format_rate(max))])
+                .style(Style::default().fg(Color::DarkGray)),
+        );
+
+    frame.render_widget(chart, area);
+}
+
+// --- DISKS RENDER ---
+fn render_disks(app: &App, frame: &mut Frame, area: Rect) {
+    if app.disk_view == DisksView::Devices {
+        render_block_devices(app, frame, area);
+        return;
+    }
+    if let Some(explorer) = &app.explorer {
+        render_explorer(app, explorer, frame, area);
+        return;
+    }
+
+    let block = CyberpunkBlock::new(" Disks ", parse_color(&app.config.theme.border_color));
+    frame.render_widget(block, area);
+
+    let inner_area = Rect::new(area.x + 1, area.y + 1, area.width - 2, area.height - 2);
+    let disks = &app.system_info.disks;
+
+    // Throughput table below the usage gauges
+    let io_devices = &app.system_info.disk_io.devices;
+    let inner_area = if io_devices.is_empty() {
+        inner_area
+    } else {
+        let io_height = (io_devices.len() as u16 + 3).min(inner_area.height / 2);
+        let split = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(3), Constraint::Length(io_height)])
+            .split(inner_area);
+        render_disk_io(app, frame, split[1]);
+        split[0]
+    };
+
+    if disks.is_empty() {
+        let p = Paragraph::new("No mounts to show (check [disks] in config.toml)")
+            .style(Style::default().fg(parse_color(&app.config.theme.value_color)))
+            .alignment(Alignment::Center);
+        frame.render_widget(p, inner_area);
+        return;
+    }
+
+    // Each mount takes a title line, a gauge line and a spacer; scroll to keep the selection visible
+    let per_disk = 3;
+    let visible = (inner_area.height / per_disk).max(1) as usize;
+    let first = app.disk_selected.saturating_sub(visible - 1);
+
+    let constraints: Vec<Constraint> = disks
+        .iter()
+        .skip(first)
+        .take(visible)
+        .map(|_| Constraint::Length(per_disk))
+        .collect();
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(constraints)
+        .split(inner_area);
+
+    for (i, (disk, chunk)) in disks.iter().skip(first).zip(chunks.iter()).enumerate() {
+        let selected = first + i == app.disk_selected;
+
+        let mut flags = Vec::new();
+        if disk.read_only {
+            flags.push("ro");
+        }
+        if disk.removable {
+            flags.push("removable");
+        }
+        let flags = if flags.is_empty() {
+            String::new()
+        } else {
+            format!(" [{}]", flags.join(", "))
+        };
+        let title = format!(
+            "{}{} {} on {}{}",
+            if selected { "> " } else { "" },
+            disk.mount_point,
+            disk.fs_type,
+            disk.device,
+            flags
+        );
+        let title_style = if selected {
+            Style::default()
+                .fg(parse_color(&app.config.theme.key_color))
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(parse_color(&app.config.theme.title_color))
+        };
+
+        let gib = 1024.0 * 1024.0 * 1024.0;
+        let inodes = disk
+            .inode_ratio()
+            .map(|r| format!(" | inodes {:.0}%", r * 100.0))
+            .unwrap_or_default();
+        let label = format!(
+            "{:.1} / {:.1} GiB ({:.0}%){}",
+            disk.used() as f64 / gib,
+            disk.total as f64 / gib,
+            disk.usage_ratio() * 100.0,
+            inodes
+        );
+        let color = if disk.usage_ratio() > 0.9 {
+            parse_color(&app.config.theme.gauge_cpu_high)
+        } else {
+            parse_color(&app.config.theme.gauge_cpu_low)
+        };
+
+        let gauge = Gauge::default()
+            .block(Block::default().title(Span::styled(title, title_style)))
+            .gauge_style(Style::default().fg(color))
+            .ratio(disk.usage_ratio().clamp(0.0, 1.0))
+            .label(label)
+            .use_unicode(true);
+        frame.render_widget(
+            gauge,
+            Rect {
+                height: chunk.height.min(2),
+                ..*chunk
+            },
+        );
+    }
+}
+
+// ncdu-like directory size explorer
+fn render_explorer(app: &App, explorer: &DirExplorer, frame: &mut Frame, area: Rect) {
+    let title = format!(" Explorer: {} ", explorer.current_path().display());
+    frame.render_widget(
+        CyberpunkBlock::new(title, parse_color(&app.config.theme.border_color)),
+        area,
+    );
+
+    let inner_area = Rect::new(area.x + 1, area.y + 1, area.width - 2, area.height - 2);
+    let gib = 1024.0 * 1024.0 * 1024.0;
+
+    if let Some(error) = &explorer.scan.error {
+        frame.render_widget(
+            Paragraph::new(format!("Scan failed: {}", error))
+                .style(Style::default().fg(parse_color(&app.config.theme.gauge_cpu_high))),
+            inner_area,
+        );
+        return;
+    }
+
+    let Some(dir) = explorer.current() else {
+        // Still scanning: progress against the mount's used space
+        let progress = &explorer.scan.progress;
+        let bytes = progress.bytes.load(std::sync::atomic::Ordering::Relaxed);
+        let entries = progress.entries.load(std::sync::atomic::Ordering::Relaxed);
+        let skipped = progress.skipped.load(std::sync::atomic::Ordering::Relaxed);
+        let used = app
+            .system_info
+            .disks
+            .iter()
+            .find(|d| std::path::Path::new(&d.mount_point) == explorer.scan.root)
+            .map_or(0, |d| d.used());
+        let ratio = if used > 0 {
+            (bytes as f64 / used as f64).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+
+        let gauge = Gauge::default()
+            .block(Block::default().title("Scanning..."))
+            .gauge_style(Style::default().fg(parse_color(&app.config.theme.gauge_cpu_low)))
+            .ratio(ratio)
+            .label(format!(
+                "{:.2} GiB, {} entries, {} unreadable",
+                bytes as f64 / gib,
+                entries,
+                skipped
+            ));
+        let gauge_area = Rect {
+            height: inner_area.height.min(3),
+            ..inner_area
+        };
+        frame.render_widget(gauge, gauge_area);
+        return;
+    };
+
+    let header = Row::new(vec!["Size", "%", "", "Name"])
+        .style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )
+        .bottom_margin(1);
+
+    let bar = |size: u64| {
+        let ratio = if dir.size > 0 {
+            size as f64 / dir.size as f64
+        } else {
+            0.0
+        };
+        let filled = (ratio * 10.0).round() as usize;
+        (
+            ratio,
+            format!("{}{}", "█".repeat(filled), "░".repeat(10 - filled)),
+        )
+    };
+
+    let format_size = |size: u64| {
+        if size as f64 >= gib {
+            format!("{:.1} GiB", size as f64 / gib)
+        } else {
+            format!("{:.1} MiB", size as f64 / 1024.0 / 1024.0)
+        }
+    };
+
+    // Keep the selection in view
+    let visible = inner_area.height.saturating_sub(2) as usize;
+    let first = explorer.selected.saturating_sub(visible.saturating_sub(1));
+
+    let mut rows: Vec<Row> = dir
+        .children
+        .iter()
+        .enumerate()
+        .skip(first)
+        .map(|(i, child)| {
+            let (ratio, bar) = bar(child.size);
+            let style = if i == explorer.selected {
+                Style::default().fg(Color::Black).bg(Color::Cyan)
+            } else {
+                Style::default().fg(parse_color(&app.config.theme.text_color))
+            };
+            Row::new(vec![
+                format_size(child.size),
+                format!("{:.1}%", ratio * 100.0),
+                bar,
+                format!("{}/", child.name),
+            ])
+            .style(style)
+        })
+        .collect();
+
+    // Files directly in this directory, summed up like ncdu's "<files>"
+    if dir.own_files > 0 {
+        let (ratio, bar) = bar(dir.own_files_size);
+        rows.push(
+            Row::new(vec![
+                format_size(dir.own_files_size),
+                format!("{:.1}%", ratio * 100.0),
+                bar,
+                format!("({} files)", dir.own_files),
+            ])
+            .style(Style::default().fg(Color::DarkGray)),
+        );
+    }
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(10), // Size
+            Constraint::Length(6),  // %
+            Constraint::Length(10), // Bar
+            Constraint::Min(10),    // Name
+        ],
+    )
+    .header(header)
+    .column_spacing(1);
+
+    frame.render_widget(table, inner_area);
+}
+
+// lsblk-like tree of /sys/block
+fn render_block_devices(app: &App, frame: &mut Frame, area: Rect) {
+    let block = CyberpunkBlock::new(
+        " Block Devices ",
+        parse_color(&app.config.theme.border_color),
+    );
+    frame.render_widget(block, area);
+
+    let inner_area = Rect::new(area.x + 1, area.y + 1, area.width - 2, area.height - 2);
+
+    // Flatten the tree into (prefix, device) rows with box-drawing branches
+    fn flatten<'a>(
+        devices: &'a [BlockDevice],
+        indent: Option<&str>,
+        out: &mut Vec<(String, &'a BlockDevice)>,
+    ) {
+        for (i, dev) in devices.iter().enumerate() {
+            let last = i + 1 == devices.len();
+            let (branch, next_indent) = match indent {
+                // Top level devices have no branch
+                None => (String::new(), String::new()),
+                Some(indent) if last => (format!("{}└─", indent), format!("{}  ", indent)),
+                Some(indent) => (format!("{}├─", indent), format!("{}│ ", indent)),
+            };
+            out.push((branch, dev));
+            flatten(&dev.children, Some(&next_indent), out);
+        }
+    }
+    let mut flat = Vec::new();
+    flatten(&app.system_info.block_devices, None, &mut flat);
+
+    let header = Row::new(vec![
+        "Name",
+        "Type",
+        "Size",
+        "Media",
+        "RM",
+        "Model",
+        "Mountpoint",
+    ])
+    .style(
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+    )
+    .bottom_margin(1);
+
+    let gib = 1024.0 * 1024.0 * 1024.0;
+    let rows: Vec<Row> = flat
+        .iter()
+        .skip(app.block_device_scroll.min(flat.len().saturating_sub(1)))
+        .map(|(prefix, dev)| {
+            let color = match dev.kind {
+                // Flag encryption layers so they stand out in the tree
+                BlockDeviceKind::Crypt => Color::Yellow,
+                BlockDeviceKind::Disk => parse_color(&app.config.theme.key_color),
+                _ => parse_color(&app.config.theme.text_color),
+            };
+            let kind = if dev.kind == BlockDeviceKind::Crypt {
+                "crypt [enc]".to_string()
+            } else {
+                dev.kind.label().to_string()
+            };
+            Row::new(vec![
+                format!("{}{}", prefix, dev.display_name()),
+                kind,
+                format!("{:.1}G", dev.size as f64 / gib),
+                dev.media.label().to_string(),
+                if dev.removable { "1" } else { "0" }.to_string(),
+                dev.model.clone().unwrap_or_default(),
+                dev.mountpoint.clone().unwrap_or_default(),
+            ])
+            .style(Style::default().fg(color))
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(24), // Name
+            Constraint::Length(11), // Type
+            Constraint::Length(9),  // Size
+            Constraint::Length(5),  // Media
+            Constraint::Length(2),  // RM
+            Constraint::Length(22), // Model
+            Constraint::Min(10),    // Mountpoint
+        ],
+    )
+    .header(header)
+    .column_spacing(1);
+
+    frame.render_widget(table, inner_area);
+}
+
+fn render_disk_io(app: &App, frame: &mut Frame, area: Rect) {
+    let header = Row::new(vec![
+        "Device", "Read/s", "Write/s", "R IOPS", "W IOPS", "R Await", "W Await", "Busy", "History",
+    ])
+    .style(
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+    );
+
+    let history_width = area.width.saturating_sub(90).max(10) as usize;
+    let rows: Vec<Row> = app
+        .system_info
+        .disk_io
+        .devices
+        .iter()
+        .map(|dev| {
+            let color = if dev.busy_percent > 80.0 {
+                parse_color(&app.config.theme.gauge_cpu_high)
+            } else {
+                parse_color(&app.config.theme.text_color)
+            };
+            Row::new(vec![
+                dev.device.clone(),
+                format_rate(dev.read_bytes_per_sec),
+                format_rate(dev.write_bytes_per_sec),
+                format!("{:.0}", dev.read_iops),
+                format!("{:.0}", dev.write_iops),
+                format!("{:.1} ms", dev.read_await_ms),
+                format!("{:.1} ms", dev.write_await_ms),
+                format!("{:.0}%", dev.busy_percent),
+                sparkline_text(&dev.history, history_width),
+            ])
+            .style(Style::default().fg(color))
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(10), // Device
+            Constraint::Length(11), // Read/s
+            Constraint::Length(11), // Write/s
+            Constraint::Length(7),  // R IOPS
+            Constraint::Length(7),  // W IOPS
+            Constraint::Length(8),  // R Await
+            Constraint::Length(8),  // W Await
+            Constraint::Length(5),  // Busy
+            Constraint::Min(10),    // History
+        ],
+    )
+    .header(header)
+    .column_spacing(2)
+    .block(Block::default().title(" I/O ").borders(Borders::TOP));
+
+    frame.render_widget(table, area);
+}
+
+/// "Load 0.52 0.58 0.59 | PSI cpu 1.2 mem 0.0 io 3.4 | run 2 blk 0", colored by severity
+fn pressure_strip(app: &App) -> Line<'static> {
+    let pressure = &app.system_info.pressure;
+    let level_color = |level: Level| match level {
+        Level::Ok => parse_color(&app.config.theme.gauge_cpu_low),
+        Level::Warn => Color::Yellow,
+        Level::Critical => parse_color(&app.config.theme.gauge_cpu_high),
+    };
+    let key = Style::default().fg(parse_color(&app.config.theme.key_color));
+    let separator = Span::styled(" │ ", Style::default().fg(Color::DarkGray));
+
+    let load_style = Style::default().fg(level_color(
+        pressure.load_level(app.system_info.cpu_info.cores),
+    ));
+    let mut spans = vec![
+        Span::styled("Load ", key),
+        Span::styled(
+            format!(
+                "{:.2} {:.2} {:.2}",
+                pressure.load[0], pressure.load[1], pressure.load[2]
+            ),
+            load_style,
+        ),
+    ];
+
+    let psi: [(&str, &Option<Psi>); 3] = [
+        ("cpu", &pressure.cpu),
+        ("mem", &pressure.memory),
+        ("io", &pressure.io),
+    ];
+    if psi.iter().any(|(_, p)| p.is_some()) {
+        spans.push(separator.clone());
+        spans.push(Span::styled("PSI", key));
+        for (name, p) in psi {
+            let Some(p) = p else {
+                continue;
+            };
+            spans.push(Span::raw(format!(" {} ", name)));
+            spans.push(Span::styled(
+                format!("{:.1}", p.some.avg10),
+                Style::default().fg(level_color(p.level())),
+            ));
+        }
+    }
+
+    spans.push(separator);
+    spans.push(Span::styled("run ", key));
+    spans.push(Span::raw(pressure.procs_running.to_string()));
+    spans.push(Span::styled(" blk ", key));
+    let blocked_color = if pressure.procs_blocked > 0 {
+        Color::Yellow
+    } else {
+        parse_color(&app.config.theme.text_color)
+    };
+    spans.push(Span::styled(
+        pressure.procs_blocked.to_string(),
+        Style::default().fg(blocked_color),
+    ));
+
+    Line::from(spans)
+}
+
+/// Stacked used / buffers / cache / free bar with a legend underneath
+fn render_memory_bar(app: &App, frame: &mut Frame, area: Rect) {
+    let breakdown = &app.system_info.memory.breakdown;
+    let segments = [
+        (
+            "Used",
+            breakdown.used(),
+            parse_color(&app.config.theme.gauge_ram),
+        ),
+        (
+            "Buffers",
+            breakdown.buffers,
+            parse_color(&app.config.theme.key_color),
+        ),
+        (
+            "Cache",
+            breakdown.cached,
+            parse_color(&app.config.theme.value_color),
+        ),
+        ("Free", breakdown.free, Color::DarkGray),
+    ];
+
+    // Cumulative rounding so the segments always add up to the full width
+    let width = area.width as u64;
+    let mut bar = Vec::new();
+    let mut filled = 0;
+    let mut cumulative = 0;
+    for (_, bytes, color) in &segments {
+        cumulative += bytes;
+        let end = (cumulative * width / breakdown.total.max(1)).min(width);
+        bar.push(Span::styled(
+            "█".repeat(end.saturating_sub(filled) as usize),
+            Style::default().fg(*color),
+        ));
+        filled = end.max(filled);
+    }
+
+    let legend: Vec<Span> = segments
+        .iter()
+        .flat_map(|(label, bytes, color)| {
+            [
+                Span::styled("■ ", Style::default().fg(*color)),
+                Span::styled(
+                    format!("{} {}  ", label, format_size(*bytes)),
+                    Style::default().fg(parse_color(&app.config.theme.text_color)),
+                ),
+            ]
+        })
+        .collect();
+
+    // Same definition of used as the segment, not sysinfo's total - available
+    let title = format!(
+        "Memory Usage ({} / {})",
+        format_size(breakdown.used()),
+        format_size(breakdown.total)
+    );
+    let paragraph = Paragraph::new(vec![Line::from(bar), Line::from(legend)])
+        .block(Block::default().title(title));
+    frame.render_widget(paragraph, area);
+}
+
+/// How the per-core CPU view draws each core, from most to least detailed
+#[derive(Clone, Copy)]
+enum CoreLayout {
+    /// "12 █████▌   63%" cells, `columns` per row
+    Bars { columns: usize },
+    /// One block character per core
+    Blocks { per_row: usize },
+    /// Two cores per braille character, four dot levels each
+    Braille { per_row: usize },
+}
+
+/// Width of a Bars cell: index, bar, percentage and a gap
+const CORE_CELL_WIDTH: usize = 17;
+const CORE_BAR_WIDTH: usize = 8;
+
+/// Picks the most detailed layout that fits, and the rows it needs.
+/// Machines with 64+ threads always get the braille heatmap.
+fn core_layout(cores: usize, width: u16, max_rows: u16) -> (CoreLayout, u16) {
+    let width = width.max(1) as usize;
+    let rows_for = |per_row: usize| cores.div_ceil(per_row.max(1)) as u16;
+
+    if cores < 64 {
+        let columns = ((width + 1) / CORE_CELL_WIDTH).max(1);
+        if rows_for(columns) <= max_rows {
+            return (CoreLayout::Bars { columns }, rows_for(columns));
+        }
+        let per_row = (width / 2).max(1);
+        if rows_for(per_row) <= max_rows {
+            return (CoreLayout::Blocks { per_row }, rows_for(per_row));
+        }
+    }
+    let per_row = width * 2;
+    (
+        CoreLayout::Braille { per_row },
+        rows_for(per_row).min(max_rows),
+    )
+}
+
+/// Blends gauge_cpu_low into gauge_cpu_high with usage. Named and indexed
+/// colors can't be blended, so those switch at 80% like the CPU gauge.
+fn usage_color(app: &App, usage: f32) -> Color {
+    let low = parse_color(&app.config.theme.gauge_cpu_low);
+    let high = parse_color(&app.config.theme.gauge_cpu_high);
+    match (low, high) {
+        (Color::Rgb(r1, g1, b1), Color::Rgb(r2, g2, b2)) => {
+            let t = (usage / 100.0).clamp(0.0, 1.0);
+            let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+            Color::Rgb(mix(r1, r2), mix(g1, g2), mix(b1, b2))
+        }
+        _ if usage > 80.0 => high,
+        _ => low,
+    }
+}
+
+/// Horizontal bar with eighth-block resolution, padded to `width`
+fn eighths_bar(ratio: f32, width: usize) -> String {
+    const PARTIAL: [char; 8] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+    let eighths = (ratio.clamp(0.0, 1.0) * (width * 8) as f32).round() as usize;
+    let mut bar = "█".repeat(eighths / 8);
+    if eighths / 8 < width {
+        bar.push(PARTIAL[eighths % 8]);
+    }
+    format!("{:<width$}", bar, width = width)
+}
+
+fn render_per_core(app: &App, frame: &mut Frame, area: Rect, layout: CoreLayout, is_compact: bool) {
+    const BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let usage = &app.system_info.core_usage;
+    let dim = Style::default().fg(Color::DarkGray);
+
+    let lines: Vec<Line> = match layout {
+        CoreLayout::Bars { columns } => usage
+            .chunks(columns)
+            .enumerate()
+            .map(|(row, cores)| {
+                let spans = cores.iter().enumerate().flat_map(|(col, &pct)| {
+                    let color = usage_color(app, pct);
+                    [
+                        Span::styled(format!("{:>2} ", row * columns + col), dim),
+                        Span::styled(
+                            eighths_bar(pct / 100.0, CORE_BAR_WIDTH),
+                            Style::default().fg(color).bg(Color::DarkGray),
+                        ),
+                        Span::styled(format!("{:>4.0}% ", pct), Style::default().fg(color)),
+                    ]
+                });
+                Line::from(spans.collect::<Vec<_>>())
+            })
+            .collect(),
+        CoreLayout::Blocks { per_row } => usage
+            .chunks(per_row)
+            .map(|cores| {
+                let spans = cores.iter().map(|&pct| {
+                    let level = ((pct / 100.0).clamp(0.0, 1.0) * 7.0).round() as usize;
+                    Span::styled(
+                        format!("{} ", BLOCKS[level]),
+                        Style::default().fg(usage_color(app, pct)),
+                    )
+                });
+                Line::from(spans.collect::<Vec<_>>())
+            })
+            .collect(),
+        CoreLayout::Braille { per_row } => {
+            // Dots filled bottom-up: left column for the first core, right for the second
+            const LEFT: [u32; 4] = [0x40, 0x04, 0x02, 0x01];
+            const RIGHT: [u32; 4] = [0x80, 0x20, 0x10, 0x08];
+            let dots = |pct: f32, column: &[u32; 4]| -> u32 {
+                let level = ((pct / 25.0).round() as usize).min(4);
+                column[..level].iter().sum()
+            };
+            // Without room for every row, the last cells make way for a "+N more" marker
+            let rows = area.height.saturating_sub(1).max(1) as usize;
+            let (shown, marker) = if usage.len() > rows * per_row {
+                let marker_width = format!(" +{} more", usage.len()).len();
+                let shown = (rows * per_row).saturating_sub(marker_width * 2) & !1;
+                (shown, Some(format!(" +{} more", usage.len() - shown)))
+            } else {
+                (usage.len(), None)
+            };
+            let mut lines: Vec<Line> = usage[..shown]
+                .chunks(per_row)
+                .map(|cores| {
+                    let spans = cores.chunks(2).map(|pair| {
+                        let left = pair[0];
+                        let right = pair.get(1).copied().unwrap_or(0.0);
+                        let bits = dots(left, &LEFT) + dots(right, &RIGHT);
+                        let cell = char::from_u32(0x2800 + bits).unwrap_or(' ');
+                        Span::styled(
+                            cell.to_string(),
+                            Style::default().fg(usage_color(app, left.max(right))),
+                        )
+                    });
+                    Line::from(spans.collect::<Vec<_>>())
+                })
+                .collect();
+            if let Some(marker) = marker {
+                let marker = Span::styled(marker, dim);
+                match lines.last_mut() {
+                    Some(line) if line.width() + marker.width() <= per_row / 2 => {
+                        line.spans.push(marker)
+                    }
+                    _ => lines.push(Line::from(marker)),
+                }
+            }
+            lines
+        }
+    };
+
+    // Keep the aggregate the single gauge shows
+    let cgroup = &app.system_info.cgroup;
+    let aggregate = match cgroup.cpu_limit.filter(|_| cgroup.cpu_limited()) {
+        Some(cpus) => format!(
+            "{:.1}% of {:.1} CPUs [cgroup limit]",
+            app.system_info.cpu_usage, cpus
+        ),
+        None => format!("{:.1}%", app.system_info.cpu_usage),
+    };
+    let title = format!(
+        "CPU Load per Core ({} threads): {}",
+        app.system_info.core_usage.len(),
+        aggregate
+    );
+    let paragraph = if is_compact {
+        let mut lines = lines;
+        lines.insert(0, Line::from(title));
+        Paragraph::new(lines)
+    } else {
+        Paragraph::new(lines).block(Block::default().title(title))
+    };
+    frame.render_widget(paragraph, area);
+}
+
+/// Bytes per second with a human unit, e.g. "12.3 MB/s"
+fn format_rate(bytes_per_sec: f64) -> String {
+    if bytes_per_sec >= 1024.0 * 1024.0 {
+        format!("{:.1} MB/s", bytes_per_sec / 1024.0 / 1024.0)
+    } else {
+        format!("{:.1} KB/s", bytes_per_sec / 1024.0)
+    }
+}
+
+/// Renders the last `width` values as block characters scaled to the max value
+fn sparkline_text<'a>(values: impl IntoIterator<Item = &'a f64>, width: usize) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let values: Vec<f64> = values.into_iter().copied().collect();
+    let values = &values[values.len().saturating_sub(width)..];
+    let max = values.iter().copied().fold(0.0, f64::max);
+
+    values
+        .iter()
+        .map(|&v| {
+            if max <= 0.0 {
+                BARS[0]
+            } else {
+                BARS[((v / max) * (BARS.len() - 1) as f64).round() as usize]
+            }
+        })
+        .collect()
+}
+
+// --- PACKAGE HISTORY RENDER ---
+fn render_packages(app: &App, frame: &mut Frame, area: Rect) {
+    let block = CyberpunkBlock::new(
+        " Package History ",
+        parse_color(&app.config.theme.border_color),
+    );
+    frame.render_widget(block, area);
+
+    let inner_area = Rect::new(area.x + 1, area.y + 1, area.width - 2, area.height - 2);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(3)])
+        .split(inner_area);
+
+    // Summary: installed counts and last full upgrade
+    let source = match &app.system_info.package_history {
+        Some(history) => format!("{} ({})", history.manager.label(), history.log_path),
+        None if app.system_info.package_history_pending => "Reading logs...".to_string(),
+        None => "No supported package log found".to_string(),
+    };
+    let summary = format!(
+        "Installed: {}\nLast full upgrade: {}\nSource: {}",
+        app.system_info.get_formatted_packages(),
+        app.system_info.get_formatted_last_upgrade(),
+        source
+    );
+    let p = Paragraph::new(summary)
+        .style(Style::default().fg(parse_color(&app.config.theme.value_color)))
+        .wrap(Wrap { trim: true });
+    frame.render_widget(p, chunks[0]);
+
+    let header = Row::new(vec!["Date", "Action", "Package", "Version"])
+        .style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )
+        .bottom_margin(1);
+
+    let rows: Vec<Row> = app
+        .system_info
+        .package_history
+        .iter()
+        .flat_map(|h| h.transactions.iter())
+        .skip(app.package_scroll)
+        .map(|t| {
+            let color = match t.action {
+                TransactionAction::Installed => Color::Green,
+                TransactionAction::Upgraded => parse_color(&app.config.theme.text_color),
+                TransactionAction::Downgraded | TransactionAction::Reinstalled => Color::Yellow,
+                TransactionAction::Removed => Color::Red,
+            };
+
+            Row::new(vec![
+                t.timestamp.clone(),
+                t.action.label().to_string(),
+                t.package.clone(),
+                t.version.clone(),
+            ])
+            .style(Style::default().fg(color))
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(16),     // Date
+            Constraint::Length(11),     // Action
+            Constraint::Percentage(35), // Package
+            Constraint::Min(10),        // Version
+        ],
+    )
+    .header(header)
+    .column_spacing(2);
+
+    frame.render_widget(table, chunks[1]);
 }
 
 // --- SETTINGS RENDER ---
@@ -631,6 +1843,14 @@ fn render_settings(app: &App, frame: &mut Frame, area: Rect) {
                 "No".to_string()
             },
         ),
+        (
+            "Disk I/O Gauge",
+            if app.config.show_disk_io_gauge {
+                "Yes".to_string()
+            } else {
+                "No".to_string()
+            },
+        ),
     ];
 
     let rows: Vec<Row> = options
@@ -658,12 +1878,21 @@ fn render_settings(app: &App, frame: &mut Frame, area: Rect) {
 
 // --- TAB BAR RENDER ---
 fn render_tabs(app: &App, frame: &mut Frame, area: Rect) {
-    let tabs = vec!["Dashboard", "Processes", "Network", "Settings"];
+    let tabs = vec![
+        "Dashboard",
+        "Processes",
+        "Network",
+        "Disks",
+        "Packages",
+        "Settings",
+    ];
     let selected_index = match app.current_tab {
         AppTab::Dashboard => 0,
         AppTab::Processes => 1,
         AppTab::Network => 2,
-        AppTab::Settings => 3,
+        AppTab::Disks => 3,
+        AppTab::Packages => 4,
+        AppTab::Settings => 5,
     };
 
     let tabs_widget = Tabs::new(tabs)
@@ -686,9 +1915,17 @@ fn render_tabs(app: &App, frame: &mut Frame, area: Rect) {
 
 fn render_footer(app: &App, frame: &mut Frame, area: Rect) {
     let hints = match app.current_tab {
-        AppTab::Dashboard => "q: Quit | ?: Help | 1-4: Tabs",
+        AppTab::Dashboard => "q: Quit | c: Per-core CPU | ?: Help | 1-6: Tabs",
         AppTab::Processes => "j/k: Scroll | s: Sort | ?: Help",
-        AppTab::Network => "?: Help",
+        AppTab::Network if app.network_view == NetworkView::Connections => {
+            "j/k: Scroll | f: Filter State | l: Listening | v: Interfaces | ?: Help"
+        }
+        AppTab::Network => "j/k: Select Interface | v: Connections | ?: Help",
+        AppTab::Disks if app.explorer.is_some() => {
+            "j/k: Select | Enter: Open | Backspace: Up | Esc: Close | ?: Help"
+        }
+        AppTab::Disks => "j/k: Select | Enter: Explore | v: Usage/Devices | ?: Help",
+        AppTab::Packages => "j/k: Scroll | ?: Help",
         AppTab::Settings => "Enter: Toggle | j/k: Nav | ?: Help",
     };
 